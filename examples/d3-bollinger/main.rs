@@ -20,6 +20,7 @@ use bucky::annotated::Annotatable;
 use bucky::array::extent::Extent;
 use bucky::error::Result;
 use bucky::scale::{ScaleContinuous, ScaleTime, ScaleLog};
+use bucky::shape::area::Area;
 use bucky::shape::line::Line;
 use bucky::interpolate::{RangeInterpolator, RoundInterpolator};
 
@@ -174,7 +175,7 @@ fn main() -> Result<()> {
         .attr("transform", format!("translate({},0)", CHART.margins.left));
 
     {
-        let tick_values = y.domain.ticks(Some(10));
+        let tick_values = y.ticks(Some(10));
 
         y_axis.append(Element::bare("text", ""))
             .set_attr("font-family", "B612 Mono")
@@ -185,7 +186,6 @@ fn main() -> Result<()> {
             .set_attr("transform", format!("translate(0, {})", y.scale(tick_values.last().cloned().unwrap())))
             .append_text_node("$ Close");
 
-        use bucky::array::ticks::Ticks;
         let y_ticks = tick_values.into_iter().annotate("g", |builder, datum| {
             let mut tick = builder
                 .attr("class", "y axis tick")
@@ -227,6 +227,19 @@ fn main() -> Result<()> {
         .y(Box::new(|datum, _i| y.scale(*datum)))
         .defined(Box::new(|datum, _i| !datum.is_nan()));
 
+    let mut area = Area::<usize>::new()
+        .x(Box::new(|_datum, i| x.scale(data[i].date.and_time(beginning_of_day))))
+        .y0(Box::new(|_datum, i| y.scale(bands.lower[i])))
+        .y1(Some(Box::new(|_datum, i| y.scale(bands.upper[i]))))
+        .defined(Box::new(|_datum, i| !bands.lower[i].is_nan()));
+
+    let indices : Vec<usize> = (0..data.len()).collect();
+
+    let band_area = Element::builder("path", "")
+        .attr("fill", "grey")
+        .attr("fill-opacity", 0.1)
+        .attr("d", area.generate(indices.as_slice()));
+
     let neutral_line = Element::builder("path", "")
         .attr("stroke", "grey")
         .attr("fill", "none")
@@ -266,6 +279,7 @@ fn main() -> Result<()> {
         .attr("overflow", "visible")
         .extend(x_axis.build())
         .extend(y_axis.build())
+        .extend(band_area.build())
         .extend(neutral_line.build())
         .extend(green_line.build())
         .extend(blue_line.build())
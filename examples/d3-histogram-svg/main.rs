@@ -12,7 +12,6 @@ use svg::{Document, Node};
 use bucky::{ Margins, Chart };
 use bucky::array::extent::Extent;
 use bucky::array::histogram::Histogram;
-use bucky::array::ticks::Ticks;
 use bucky::error::Result;
 use bucky::scale::{ScaleContinuous, ScaleLinear};
 
@@ -60,7 +59,7 @@ fn main() -> Result<()> {
         .range(CHART.margins.left..CHART.width - CHART.margins.right)?;
 
     let bins = Histogram::new(&data)
-        .thresholds(Some(&x.domain.ticks(Some(40))))
+        .thresholds(Some(&x.ticks(Some(40))))
         .histogram(|d| d.rate);
     let last_bin = bins.iter().max_by(|a, b| a.values.len().cmp(&b.values.len())).unwrap();
 
@@ -109,8 +108,8 @@ fn main() -> Result<()> {
             .set("font-size", "5pt")
             .set("y", "-4em")
             .set("text-anchor", "end")
-            .set("x", x.scale(x.domain.end))
-            .set("transform", format!("translate(0, {})",  y.scale(y.domain.end)))
+            .set("x", x.scale(x.extent().end))
+            .set("transform", format!("translate(0, {})",  y.scale(y.extent().end)))
             .add(title_node);
         group.append(title);
 
@@ -123,7 +122,7 @@ fn main() -> Result<()> {
             .set("y2", 1);
         group.append(line);
 
-        let tick_values = x.domain.ticks(Some(CHART.width / 80));
+        let tick_values = x.ticks(Some(CHART.width / 80));
         let mut ticks = element::Group::new()
             .set("transform", format!("translate(0, {})", CHART.margins.top))
             .set("class", "x axis tick-container");
@@ -161,7 +160,7 @@ fn main() -> Result<()> {
             .set("font-size", "5pt")
             .set("dy", "0.32em")
             .set("x", 4)
-            .set("transform", format!("translate(0, {})",  y.scale(y.domain.end)));
+            .set("transform", format!("translate(0, {})",  y.scale(y.extent().end)));
         group.append(title);
 
         let line = element::Line::new()
@@ -174,7 +173,7 @@ fn main() -> Result<()> {
 
         group.append(line);
 
-        let tick_values = y.domain.ticks(Some(10));
+        let tick_values = y.ticks(Some(10));
         let mut ticks = element::Group::new()
             .set("class", "y axis tick-container");
         ticks = tick_values.iter().fold(ticks, |acc, d| {
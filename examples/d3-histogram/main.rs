@@ -126,8 +126,8 @@ fn main() -> Result<()> {
         .set_attr("font-size", "5pt")
         .set_attr("y", "-4em")
         .set_attr("text-anchor", "end")
-        .set_attr("x", x.scale(x.domain.end))
-        .set_attr("transform", format!("translate(0, {})",  y.scale(y.domain.end)))
+        .set_attr("x", x.scale(x.extent().end))
+        .set_attr("transform", format!("translate(0, {})",  y.scale(y.extent().end)))
         .append_text_node("Unemployment (%)");
 
     x_axis.append(Element::bare("line", ""))
@@ -169,7 +169,7 @@ fn main() -> Result<()> {
         .set_attr("font-size", "5pt")
         .set_attr("dy", "0.32em")
         .set_attr("x", 4)
-        .set_attr("transform", format!("translate(0, {})",  y.scale(y.domain.end)))
+        .set_attr("transform", format!("translate(0, {})",  y.scale(y.extent().end)))
         .append_text_node("Counties (#)");
 
     y_axis.append(Element::bare("line", ""))
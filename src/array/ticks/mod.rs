@@ -15,9 +15,9 @@ pub trait Ticks {
 }
 
 pub trait TickIncrement<DomainType, ReturnType> {
-    /// Like tick_step, except requires that *start* is always less than or
-    /// equal to *step*, and if the tick step for the given *start*, *stop*
-    /// and *count* would be less than one, returns the negative inverse tick
+    /// Like tick_step, except the sign doesn't depend on the order of *start*
+    /// and *stop*, and if the tick step for the given *start*, *stop* and
+    /// *count* would be less than one, returns the negative inverse tick
     /// step instead. This method is always guaranteed to return an integer,
     /// and is used by d3.ticks to guarantee that the returned tick
     /// values are represented as precisely as possible in IEEE 754 floating
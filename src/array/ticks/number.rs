@@ -12,19 +12,22 @@ where
         let stop = self.end.into();
         let count = count.unwrap_or(0);
 
-        // let reverse : bool;
-        // let mut i : f64 = (-1).into();
-        let ticks: Vec<f64>;
+        let mut ticks: Vec<f64>;
 
         if start == stop && count > 0 {
             return vec![start];
         }
 
-        // TODO: Implement reversing
-        // if (reverse = stop < start) n = start, start = stop, stop = n;
+        // The ticks of a descending range are those of the ascending range,
+        // reversed.
+        let reverse = stop < start;
+        let (start, stop) = match reverse {
+            true => (stop, start),
+            false => (start, stop),
+        };
 
-        let step = self.tick_increment(count);
-        if step == 0.0 {
+        let step = (start..stop).tick_increment(count);
+        if step == 0.0 || !step.is_finite() {
             return vec![];
         }
 
@@ -40,6 +43,10 @@ where
             ticks = (0..n).map(|i| (tick_start - i as f64) / step).collect();
         }
 
+        if reverse {
+            ticks.reverse();
+        }
+
         return ticks;
     }
 }
@@ -95,7 +102,7 @@ where
         let start: f64 = self.start.into();
         let stop: f64 = self.end.into();
 
-        let step: f64 = (stop - start)
+        let step: f64 = (stop - start).abs()
             / f64::try_from(std::cmp::max(0, count))
                 .expect("Can't fit that many ticks into an f64");
 
@@ -211,4 +218,23 @@ mod tests {
         assert_eq!((-10..10).ticks(Some(2)), vec![-10.0, 0.0, 10.0]);
         assert_eq!((-10..10).ticks(Some(1)), vec![0.0,]);
     }
+
+    #[test]
+    fn reversed_when_start_gt_stop() {
+        assert_eq!(
+            vec![1.0, 0.9, 0.8, 0.7, 0.6, 0.5, 0.4, 0.3, 0.2, 0.1, 0.0],
+            (1..0).ticks(Some(10))
+        );
+        assert_eq!((1..0).ticks(Some(3)), vec![1.0, 0.5, 0.0]);
+        assert_eq!((10..0).ticks(Some(5)), vec![10.0, 8.0, 6.0, 4.0, 2.0, 0.0]);
+        assert_eq!((10..-10).ticks(Some(6)), vec![10.0, 5.0, 0.0, -5.0, -10.0]);
+    }
+
+    #[test]
+    fn tick_increment_ignores_direction() {
+        assert_eq!(0.1, -1.0 / (0..1).tick_increment(10));
+        assert_eq!((0..1).tick_increment(10), (1..0).tick_increment(10));
+        assert_eq!(2.0, (10..-10).tick_increment(10));
+        assert_eq!(2.0, (-10..10).tick_increment(10));
+    }
 }
@@ -1,4 +1,24 @@
-#[derive(Debug)]
+//! Axes render human-readable reference marks for scales.
+//!
+//! An axis consists of a path element of class "domain" representing the
+//! extent of the scale's domain, followed by a g element of class "tick" for
+//! each of the scale's ticks. Each tick has a line element to draw the tick
+//! line, and a text element for the tick label.
+
+use std::ops::Range;
+
+#[cfg(feature = "dom-minidom")]
+use crate::annotated::Annotatable;
+use crate::array::ticks::TickStep;
+use crate::interpolate::RangeInterpolator;
+use crate::scale::{
+    ScaleBand, ScaleContinuous, ScaleIdentity, ScaleLinear, ScaleLog, ScalePoint, ScalePow,
+    ScaleRadial, ScaleSymlog,
+};
+#[cfg(feature = "time")]
+use crate::scale::ScaleTime;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum AxisOrientation {
     Left,
     Top,
@@ -6,4 +26,660 @@ pub enum AxisOrientation {
     Bottom,
 }
 
-pub struct Axis {}
+/// Implement this trait to allow a scale to be rendered by an [Axis](Axis).
+pub trait AxisScale {
+    type Tick;
+
+    /// Returns the tick values the axis should display when none are given
+    /// explicitly.
+    fn axis_ticks(&self, count: Option<i32>) -> Vec<Self::Tick>;
+
+    /// Returns the tick labels the axis should display for the given ticks when
+    /// no tick format has been given explicitly.
+    fn axis_tick_labels(&self, ticks: &[Self::Tick], count: Option<i32>) -> Vec<String>;
+
+    /// Returns the position of the tick in the output range.  The offset is
+    /// the axis offset, used by discrete scales to center ticks within a band.
+    fn axis_position(&self, tick: &Self::Tick, offset: f64) -> f64;
+
+    /// Returns the first and last values of the output range.
+    fn axis_range(&self) -> Range<f64>;
+}
+
+/// `Axis` renders a scale as a group of SVG elements with the same geometry as
+/// d3-axis.
+pub struct Axis<'a, ScaleType>
+where
+    ScaleType: AxisScale,
+{
+    scale: &'a ScaleType,
+    orientation: AxisOrientation,
+    tick_count: Option<i32>,
+    tick_values: Option<Vec<ScaleType::Tick>>,
+    tick_format: Option<Box<dyn Fn(&ScaleType::Tick) -> String + 'a>>,
+    pub tick_size_inner: f64,
+    pub tick_size_outer: f64,
+    pub tick_padding: f64,
+    pub offset: f64,
+}
+
+impl<'a, ScaleType> Axis<'a, ScaleType>
+where
+    ScaleType: AxisScale,
+{
+    /// Constructs a new axis generator for the given scale and orientation,
+    /// with empty tick arguments, a tick size of 6 and padding of 3.
+    pub fn new(scale: &'a ScaleType, orientation: AxisOrientation) -> Self {
+        Self {
+            scale,
+            orientation,
+            tick_count: None,
+            tick_values: None,
+            tick_format: None,
+            tick_size_inner: 6.0,
+            tick_size_outer: 6.0,
+            tick_padding: 3.0,
+            offset: 0.5,
+        }
+    }
+
+    /// Constructs a new top-oriented axis generator: ticks are drawn above
+    /// the horizontal domain path.
+    pub fn top(scale: &'a ScaleType) -> Self {
+        Self::new(scale, AxisOrientation::Top)
+    }
+
+    /// Constructs a new right-oriented axis generator: ticks are drawn to the
+    /// right of the vertical domain path.
+    pub fn right(scale: &'a ScaleType) -> Self {
+        Self::new(scale, AxisOrientation::Right)
+    }
+
+    /// Constructs a new bottom-oriented axis generator: ticks are drawn below
+    /// the horizontal domain path.
+    pub fn bottom(scale: &'a ScaleType) -> Self {
+        Self::new(scale, AxisOrientation::Bottom)
+    }
+
+    /// Constructs a new left-oriented axis generator: ticks are drawn to the
+    /// left of the vertical domain path.
+    pub fn left(scale: &'a ScaleType) -> Self {
+        Self::new(scale, AxisOrientation::Left)
+    }
+
+    /// Sets the approximate number of ticks passed to the scale when
+    /// generating tick values and the default tick format.
+    pub fn ticks(self, tick_count: i32) -> Self {
+        Self {
+            tick_count: Some(tick_count),
+            ..self
+        }
+    }
+
+    /// Sets explicit tick values, bypassing the scale's tick generator.
+    pub fn tick_values(self, tick_values: Vec<ScaleType::Tick>) -> Self {
+        Self {
+            tick_values: Some(tick_values),
+            ..self
+        }
+    }
+
+    /// Sets the closure used to format tick labels.  If no format is set the
+    /// scale's default tick format is used.
+    pub fn tick_format(self, tick_format: Box<dyn Fn(&ScaleType::Tick) -> String + 'a>) -> Self {
+        Self {
+            tick_format: Some(tick_format),
+            ..self
+        }
+    }
+
+    /// Sets the inner and outer tick size to the same value.
+    pub fn tick_size(self, tick_size: f64) -> Self {
+        Self {
+            tick_size_inner: tick_size,
+            tick_size_outer: tick_size,
+            ..self
+        }
+    }
+
+    /// The inner tick size controls the length of the tick lines, offset from
+    /// the native position of the axis.
+    pub fn tick_size_inner(self, tick_size_inner: f64) -> Self {
+        Self {
+            tick_size_inner,
+            ..self
+        }
+    }
+
+    /// The outer tick size controls the length of the square ends of the
+    /// domain path, offset from the native position of the axis. Thus, the
+    /// “outer ticks” are not actually ticks but part of the domain path, and
+    /// their position is determined by the associated scale’s domain extent.
+    pub fn tick_size_outer(self, tick_size_outer: f64) -> Self {
+        Self {
+            tick_size_outer,
+            ..self
+        }
+    }
+
+    /// Sets the padding between the ticks and the tick labels.
+    pub fn tick_padding(self, tick_padding: f64) -> Self {
+        Self {
+            tick_padding,
+            ..self
+        }
+    }
+
+    /// Sets the pixel offset applied to the domain path and every tick.  The
+    /// default of 0.5 produces crisp edges on low resolution devices; use 0
+    /// when rendering for high resolution devices.
+    pub fn offset(self, offset: f64) -> Self {
+        Self { offset, ..self }
+    }
+
+    /// Returns the SVG path data for the domain path.
+    pub fn domain_path(&self) -> String {
+        let range = self.scale.axis_range();
+        let range0 = range.start + self.offset;
+        let range1 = range.end + self.offset;
+        let k = self.k();
+        let outer = self.tick_size_outer;
+        let offset = self.offset;
+
+        match self.orientation {
+            AxisOrientation::Left | AxisOrientation::Right => {
+                if outer != 0.0 {
+                    format!(
+                        "M{},{}H{}V{}H{}",
+                        k * outer,
+                        range0,
+                        offset,
+                        range1,
+                        k * outer
+                    )
+                } else {
+                    format!("M{},{}V{}", offset, range0, range1)
+                }
+            }
+            AxisOrientation::Top | AxisOrientation::Bottom => {
+                if outer != 0.0 {
+                    format!(
+                        "M{},{}V{}H{}V{}",
+                        range0,
+                        k * outer,
+                        offset,
+                        range1,
+                        k * outer
+                    )
+                } else {
+                    format!("M{},{}H{}", range0, offset, range1)
+                }
+            }
+        }
+    }
+
+    /// Returns each tick paired with its position along the axis (including
+    /// the offset) and its label.
+    pub fn ticks_with_labels(&self) -> Vec<(f64, String)>
+    where
+        ScaleType::Tick: Clone,
+    {
+        let values = match &self.tick_values {
+            Some(values) => values.clone(),
+            None => self.scale.axis_ticks(self.tick_count),
+        };
+
+        let labels = match &self.tick_format {
+            Some(format) => values.iter().map(|tick| format(tick)).collect(),
+            None => self.scale.axis_tick_labels(&values, self.tick_count),
+        };
+
+        values
+            .iter()
+            .zip(labels)
+            .map(|(tick, label)| (self.scale.axis_position(tick, self.offset) + self.offset, label))
+            .collect()
+    }
+
+    /// Renders the axis.  The returned group contains the domain path followed
+    /// by a `<g class="tick">` for each tick.  The caller is expected to
+    /// position the group, typically with a `transform` attribute.
+    #[cfg(feature = "dom-minidom")]
+    pub fn render(&self) -> minidom::ElementBuilder
+    where
+        ScaleType::Tick: Clone,
+    {
+        use minidom::Element;
+
+        let k = self.k();
+        let spacing = self.tick_size_inner.max(0.0) + self.tick_padding;
+        let orientation = self.orientation;
+        let tick_size_inner = self.tick_size_inner;
+
+        let text_anchor = match orientation {
+            AxisOrientation::Right => "start",
+            AxisOrientation::Left => "end",
+            _ => "middle",
+        };
+
+        let mut axis = Element::builder("g", "")
+            .attr("fill", "none")
+            .attr("font-size", 10)
+            .attr("font-family", "sans-serif")
+            .attr("text-anchor", text_anchor);
+
+        axis.append(Element::bare("path", ""))
+            .set_attr("class", "domain")
+            .set_attr("stroke", "currentColor")
+            .set_attr("d", self.domain_path());
+
+        let ticks = self.ticks_with_labels().into_iter().annotate("g", |builder, (position, label)| {
+            let transform = match orientation {
+                AxisOrientation::Top | AxisOrientation::Bottom => format!("translate({},0)", position),
+                AxisOrientation::Left | AxisOrientation::Right => format!("translate(0,{})", position),
+            };
+
+            let mut tick = builder
+                .attr("class", "tick")
+                .attr("opacity", 1)
+                .attr("transform", transform);
+
+            let (line_attr, text_attr, dy) = match orientation {
+                AxisOrientation::Top => ("y2", "y", "0em"),
+                AxisOrientation::Bottom => ("y2", "y", "0.71em"),
+                AxisOrientation::Left | AxisOrientation::Right => ("x2", "x", "0.32em"),
+            };
+
+            tick.append(Element::bare("line", ""))
+                .set_attr("stroke", "currentColor")
+                .set_attr(line_attr, k * tick_size_inner);
+
+            tick.append(Element::bare("text", ""))
+                .set_attr("fill", "currentColor")
+                .set_attr(text_attr, k * spacing)
+                .set_attr("dy", dy)
+                .append_text_node(label);
+
+            tick
+        });
+
+        axis.append_all(ticks)
+    }
+
+    fn k(&self) -> f64 {
+        match self.orientation {
+            AxisOrientation::Top | AxisOrientation::Left => -1.0,
+            AxisOrientation::Right | AxisOrientation::Bottom => 1.0,
+        }
+    }
+}
+
+/// Like d3's linear tick format, the precision is derived from the tick step so
+/// that every label has the same number of decimal places.
+fn linear_tick_labels(domain: &Range<f64>, ticks: &[f64], count: Option<i32>) -> Vec<String> {
+    let step = domain.tick_step(count.unwrap_or(10));
+    let precision = (-step.abs().log10().floor()).max(0.0) as usize;
+
+    ticks
+        .iter()
+        .map(|tick| format!("{:.*}", precision, tick))
+        .collect()
+}
+
+/// Returns the first and last stops of a polylinear domain or range.
+fn extent<T: Copy>(stops: &[T]) -> Range<T> {
+    stops[0]..stops[stops.len() - 1]
+}
+
+impl<InterpolatorType> AxisScale for ScaleLinear<f64, InterpolatorType>
+where
+    InterpolatorType: for<'a> RangeInterpolator<'a, f64>,
+{
+    type Tick = f64;
+
+    fn axis_ticks(&self, count: Option<i32>) -> Vec<f64> {
+        self.ticks(count)
+    }
+
+    fn axis_tick_labels(&self, ticks: &[f64], count: Option<i32>) -> Vec<String> {
+        linear_tick_labels(&extent(&self.domain), ticks, count)
+    }
+
+    fn axis_position(&self, tick: &f64, _offset: f64) -> f64 {
+        self.scale(*tick)
+    }
+
+    fn axis_range(&self) -> Range<f64> {
+        extent(&self.range)
+    }
+}
+
+impl<InterpolatorType> AxisScale for ScalePow<f64, InterpolatorType>
+where
+    InterpolatorType: for<'a> RangeInterpolator<'a, f64>,
+{
+    type Tick = f64;
+
+    fn axis_ticks(&self, count: Option<i32>) -> Vec<f64> {
+        self.ticks(count)
+    }
+
+    fn axis_tick_labels(&self, ticks: &[f64], count: Option<i32>) -> Vec<String> {
+        linear_tick_labels(&self.domain, ticks, count)
+    }
+
+    fn axis_position(&self, tick: &f64, _offset: f64) -> f64 {
+        self.scale(*tick)
+    }
+
+    fn axis_range(&self) -> Range<f64> {
+        self.range.clone()
+    }
+}
+
+impl AxisScale for ScaleIdentity {
+    type Tick = f64;
+
+    fn axis_ticks(&self, count: Option<i32>) -> Vec<f64> {
+        self.ticks(count)
+    }
+
+    fn axis_tick_labels(&self, ticks: &[f64], count: Option<i32>) -> Vec<String> {
+        linear_tick_labels(&self.domain, ticks, count)
+    }
+
+    fn axis_position(&self, tick: &f64, _offset: f64) -> f64 {
+        self.scale(*tick)
+    }
+
+    fn axis_range(&self) -> Range<f64> {
+        self.domain.clone()
+    }
+}
+
+impl AxisScale for ScaleRadial {
+    type Tick = f64;
+
+    fn axis_ticks(&self, count: Option<i32>) -> Vec<f64> {
+        self.ticks(count)
+    }
+
+    fn axis_tick_labels(&self, ticks: &[f64], count: Option<i32>) -> Vec<String> {
+        linear_tick_labels(&self.domain, ticks, count)
+    }
+
+    fn axis_position(&self, tick: &f64, _offset: f64) -> f64 {
+        self.scale(*tick)
+    }
+
+    fn axis_range(&self) -> Range<f64> {
+        self.range.clone()
+    }
+}
+
+impl<InterpolatorType> AxisScale for ScaleLog<f64, InterpolatorType>
+where
+    InterpolatorType: for<'a> RangeInterpolator<'a, f64>,
+{
+    type Tick = f64;
+
+    fn axis_ticks(&self, count: Option<i32>) -> Vec<f64> {
+        self.ticks(count)
+    }
+
+    fn axis_tick_labels(&self, ticks: &[f64], count: Option<i32>) -> Vec<String> {
+        ticks.iter().map(self.tick_format(count)).collect()
+    }
+
+    fn axis_position(&self, tick: &f64, _offset: f64) -> f64 {
+        self.scale(*tick)
+    }
+
+    fn axis_range(&self) -> Range<f64> {
+        extent(&self.range)
+    }
+}
+
+impl<InterpolatorType> AxisScale for ScaleSymlog<f64, InterpolatorType>
+where
+    InterpolatorType: for<'a> RangeInterpolator<'a, f64>,
+{
+    type Tick = f64;
+
+    fn axis_ticks(&self, count: Option<i32>) -> Vec<f64> {
+        self.ticks(count)
+    }
+
+    /// The ticks may be powers of ten spanning many orders of magnitude, so
+    /// each label is formatted on its own.
+    fn axis_tick_labels(&self, ticks: &[f64], _count: Option<i32>) -> Vec<String> {
+        ticks.iter().map(|tick| tick.to_string()).collect()
+    }
+
+    fn axis_position(&self, tick: &f64, _offset: f64) -> f64 {
+        self.scale(*tick)
+    }
+
+    fn axis_range(&self) -> Range<f64> {
+        self.range.clone()
+    }
+}
+
+#[cfg(feature = "time")]
+impl<InterpolatorType> AxisScale for ScaleTime<f64, InterpolatorType>
+where
+    InterpolatorType: for<'a> RangeInterpolator<'a, f64>,
+{
+    type Tick = chrono::NaiveDateTime;
+
+    fn axis_ticks(&self, count: Option<i32>) -> Vec<chrono::NaiveDateTime> {
+        self.ticks(count)
+    }
+
+    /// Uses d3's multi-scale time format: each tick is labeled with the
+    /// coarsest calendar unit it falls on.
+    fn axis_tick_labels(&self, ticks: &[chrono::NaiveDateTime], _count: Option<i32>) -> Vec<String> {
+        use chrono::{Datelike, Timelike, Weekday};
+
+        ticks
+            .iter()
+            .map(|tick| {
+                let format = if tick.nanosecond() != 0 {
+                    "%.3f"
+                } else if tick.second() != 0 {
+                    ":%S"
+                } else if tick.minute() != 0 {
+                    "%I:%M"
+                } else if tick.hour() != 0 {
+                    "%I %p"
+                } else if tick.day() != 1 {
+                    if tick.weekday() != Weekday::Sun {
+                        "%a %d"
+                    } else {
+                        "%b %d"
+                    }
+                } else if tick.month0() != 0 {
+                    "%B"
+                } else {
+                    "%Y"
+                };
+
+                tick.format(format).to_string()
+            })
+            .collect()
+    }
+
+    fn axis_position(&self, tick: &chrono::NaiveDateTime, _offset: f64) -> f64 {
+        self.scale(*tick)
+    }
+
+    fn axis_range(&self) -> Range<f64> {
+        extent(&self.range)
+    }
+}
+
+impl<'a, DomainType> AxisScale for ScaleBand<'a, DomainType>
+where
+    DomainType: Clone + Eq + std::hash::Hash + std::fmt::Display,
+{
+    type Tick = DomainType;
+
+    /// Band scales have no tick generator; every value in the domain is a tick.
+    fn axis_ticks(&self, _count: Option<i32>) -> Vec<DomainType> {
+        self.domain_values().to_vec()
+    }
+
+    fn axis_tick_labels(&self, ticks: &[DomainType], _count: Option<i32>) -> Vec<String> {
+        ticks.iter().map(|tick| tick.to_string()).collect()
+    }
+
+    /// Ticks are centered within their band, on whole pixels if the scale
+    /// is rounded.
+    fn axis_position(&self, tick: &DomainType, offset: f64) -> f64 {
+        let center = (self.band_width - offset * 2.0).max(0.0) / 2.0;
+        let center = match self.round {
+            true => center.round(),
+            false => center,
+        };
+
+        self.scale(tick) + center
+    }
+
+    fn axis_range(&self) -> Range<f64> {
+        self.range.clone()
+    }
+}
+
+impl<'a, DomainType> AxisScale for ScalePoint<'a, DomainType>
+where
+    DomainType: Clone + Eq + std::hash::Hash + std::fmt::Display,
+{
+    type Tick = DomainType;
+
+    /// Point scales have no tick generator; every value in the domain is a
+    /// tick.
+    fn axis_ticks(&self, _count: Option<i32>) -> Vec<DomainType> {
+        self.domain_values().to_vec()
+    }
+
+    fn axis_tick_labels(&self, ticks: &[DomainType], _count: Option<i32>) -> Vec<String> {
+        ticks.iter().map(|tick| tick.to_string()).collect()
+    }
+
+    fn axis_position(&self, tick: &DomainType, _offset: f64) -> f64 {
+        self.scale(tick)
+    }
+
+    fn axis_range(&self) -> Range<f64> {
+        self.range.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+
+    #[test]
+    fn bottom_domain_path() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new().range(0..100)?;
+
+        assert_eq!("M0.5,6V0.5H100.5V6", Axis::bottom(&scale).domain_path());
+        assert_eq!("M0.5,-6V0.5H100.5V-6", Axis::top(&scale).domain_path());
+        assert_eq!(
+            "M0.5,0.5H100.5",
+            Axis::bottom(&scale).tick_size_outer(0.0).domain_path()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn left_domain_path() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new().range(100..0)?;
+
+        assert_eq!("M-6,100.5H0.5V0.5H-6", Axis::left(&scale).domain_path());
+        assert_eq!("M6,100.5H0.5V0.5H6", Axis::right(&scale).domain_path());
+        assert_eq!(
+            "M0.5,100.5V0.5",
+            Axis::left(&scale).tick_size_outer(0.0).domain_path()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ticks_use_the_scale_ticks_and_format() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new().range(0..100)?;
+
+        let ticks = Axis::bottom(&scale).ticks(5).ticks_with_labels();
+        let expected = vec![
+            (0.5, "0.0".to_string()),
+            (20.5, "0.2".to_string()),
+            (40.5, "0.4".to_string()),
+            (60.5, "0.6".to_string()),
+            (80.5, "0.8".to_string()),
+            (100.5, "1.0".to_string()),
+        ];
+        assert_eq!(expected, ticks);
+
+        Ok(())
+    }
+
+    #[test]
+    fn tick_values_and_format_override_the_scale() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new().domain(0..10)?.range(0..100)?;
+
+        let ticks = Axis::bottom(&scale)
+            .offset(0.0)
+            .tick_values(vec![1.0, 5.0])
+            .tick_format(Box::new(|d| format!("{}%", d)))
+            .ticks_with_labels();
+        assert_eq!(vec![(10.0, "1%".to_string()), (50.0, "5%".to_string())], ticks);
+
+        Ok(())
+    }
+
+    #[test]
+    fn band_ticks_are_centered() {
+        let scale = ScaleBand::<&str>::new()
+            .domain(&["a", "b", "c"])
+            .range(0..120);
+
+        let ticks = Axis::bottom(&scale).offset(0.0).ticks_with_labels();
+        let expected = vec![
+            (20.0, "a".to_string()),
+            (60.0, "b".to_string()),
+            (100.0, "c".to_string()),
+        ];
+        assert_eq!(expected, ticks);
+    }
+
+    #[cfg(feature = "dom-minidom")]
+    #[test]
+    fn render_left() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new().range(100..0)?;
+
+        let axis = Axis::left(&scale).ticks(1).render().build();
+
+        assert_eq!(Some("end"), axis.attr("text-anchor"));
+
+        let children: Vec<_> = axis.children().collect();
+        assert_eq!(3, children.len());
+        assert_eq!(Some("domain"), children[0].attr("class"));
+
+        let tick = children[1];
+        assert_eq!(Some("tick"), tick.attr("class"));
+        assert_eq!(Some("translate(0,100.5)"), tick.attr("transform"));
+
+        let tick_children: Vec<_> = tick.children().collect();
+        assert_eq!("line", tick_children[0].name());
+        assert_eq!(Some("-6"), tick_children[0].attr("x2"));
+        assert_eq!("text", tick_children[1].name());
+        assert_eq!(Some("-9"), tick_children[1].attr("x"));
+        assert_eq!(Some("0.32em"), tick_children[1].attr("dy"));
+        assert_eq!("0", tick_children[1].text());
+
+        Ok(())
+    }
+}
@@ -11,6 +11,21 @@ pub enum BuckyError {
     #[error("descending scale not allowed")]
     DescendingScale,
 
+    #[error("at least two stops are required")]
+    InsufficientStops,
+
+    #[error("domain must be in ascending or descending order")]
+    NonMonotonicDomain,
+
+    #[error("range must be numeric to be inverted")]
+    NonNumericRange,
+
+    #[error("range must not be empty to be inverted")]
+    DegenerateRange,
+
+    #[error("inverted date is out of range")]
+    DateOutOfRange,
+
     #[error("Date/Time parsing error: {0}")]
     ChronoParseError(#[from] chrono::ParseError),
 }
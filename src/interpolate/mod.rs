@@ -1,3 +1,5 @@
+use crate::error::{BuckyError, Result};
+
 mod number;
 
 pub use number::*;
@@ -23,4 +25,16 @@ pub trait RangeInterpolator<'a, Output> {
     /// Typically a is equal to the range's starting value and b to the range's
     /// ending value.
     fn interpolate_range(&'a self, range: &std::ops::Range<Output>, n: f64) -> Output;
+
+    /// The inverse of [interpolate_range](RangeInterpolator::interpolate_range):
+    /// given a value between a and b, this function returns the corresponding
+    /// parameter n, such that a yields 0 and b yields 1.  Only numeric ranges
+    /// can be inverted, so by default this returns an error.
+    fn uninterpolate_range(
+        &'a self,
+        _range: &std::ops::Range<Output>,
+        _value: Output,
+    ) -> Result<f64> {
+        Err(BuckyError::NonNumericRange)
+    }
 }
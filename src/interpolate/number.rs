@@ -1,6 +1,7 @@
 use std::ops::Range;
 
 use super::RangeInterpolator;
+use crate::error::{BuckyError, Result};
 
 /// RoundInterpolator is similar to [NumberInterpolator](NumberInterpolator), except it will round
 /// the resulting value to the nearest integer.
@@ -15,6 +16,10 @@ impl<'a> RangeInterpolator<'a, f64> for RoundInterpolator {
     fn interpolate_range(&'a self, range: &Range<f64>, n: f64) -> f64 {
         (range.start * (1.0 - n) + range.end * n).round()
     }
+
+    fn uninterpolate_range(&'a self, range: &Range<f64>, value: f64) -> Result<f64> {
+        uninterpolate_number(range, value)
+    }
 }
 
 /// NumberInterpolator interpolates between two floating point (f64) values.
@@ -29,6 +34,18 @@ impl<'a> RangeInterpolator<'a, f64> for NumberInterpolator {
     fn interpolate_range(&'a self, range: &Range<f64>, n: f64) -> f64 {
         range.start * (1.0 - n) + range.end * n
     }
+
+    fn uninterpolate_range(&'a self, range: &Range<f64>, value: f64) -> Result<f64> {
+        uninterpolate_number(range, value)
+    }
+}
+
+fn uninterpolate_number(range: &Range<f64>, value: f64) -> Result<f64> {
+    if range.end == range.start {
+        return Err(BuckyError::DegenerateRange);
+    }
+
+    Ok((value - range.start) / (range.end - range.start))
 }
 
 #[cfg(test)]
@@ -66,4 +83,17 @@ mod tests {
         assert_eq!(20.0, interp.interpolate_range(&range, 0.3));
         assert_eq!(42.0, interp.interpolate_range(&range, 1.0));
     }
+
+    #[test]
+    fn uninterpolate() -> Result<()> {
+        let range = 10.0..42.0;
+        let interp = NumberInterpolator {};
+
+        assert_delta!(0.0, interp.uninterpolate_range(&range, 10.0)?, DELTA);
+        assert_delta!(0.5, interp.uninterpolate_range(&range, 26.0)?, DELTA);
+        assert_delta!(1.5, interp.uninterpolate_range(&range, 58.0)?, DELTA);
+        assert!(interp.uninterpolate_range(&(1.0..1.0), 1.0).is_err());
+
+        Ok(())
+    }
 }
@@ -73,5 +73,6 @@ pub mod axis;
 pub mod color;
 pub mod error;
 pub mod interpolate;
+pub mod path;
 pub mod scale;
 pub mod shape;
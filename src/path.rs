@@ -0,0 +1,317 @@
+//! Path contexts receive the drawing commands emitted by the shape generators.
+//!
+//! [PathContext](PathContext) mirrors the subset of the
+//! [CanvasRenderingContext2D](https://html.spec.whatwg.org/multipage/canvas.html#canvasrenderingcontext2d)
+//! API used by the generators.  [SvgPath](SvgPath) serializes those commands
+//! to SVG path data, and a pathfinder `Path2D` can be drawn into directly.
+
+use std::f64::consts::PI;
+use std::fmt;
+
+use pathfinder_canvas::Path2D;
+use pathfinder_content::outline::ArcDirection;
+use pathfinder_geometry::vector::Vector2F;
+
+const EPSILON: f64 = 1e-6;
+const TAU: f64 = 2.0 * PI;
+const TAU_EPSILON: f64 = TAU - EPSILON;
+
+pub trait PathContext {
+    /// Starts a new subpath at ⟨x, y⟩.
+    fn move_to(&mut self, x: f64, y: f64);
+    /// Draws a straight line from the current point to ⟨x, y⟩.
+    fn line_to(&mut self, x: f64, y: f64);
+    /// Draws a quadratic Bézier segment from the current point to ⟨x, y⟩
+    /// with the control point ⟨cpx, cpy⟩.
+    fn quadratic_curve_to(&mut self, cpx: f64, cpy: f64, x: f64, y: f64);
+    /// Draws a cubic Bézier segment from the current point to ⟨x, y⟩ with the
+    /// control points ⟨cpx1, cpy1⟩ and ⟨cpx2, cpy2⟩.
+    fn bezier_curve_to(&mut self, cpx1: f64, cpy1: f64, cpx2: f64, cpy2: f64, x: f64, y: f64);
+    /// Draws a circular arc segment centered at ⟨x, y⟩ with the radius `r`,
+    /// from the angle `a0` to `a1` in radians.  If the current point is not
+    /// the start of the arc a straight line is drawn to it first.  A negative
+    /// or NaN radius is treated as zero, so only that line is drawn.
+    fn arc(&mut self, x: f64, y: f64, r: f64, a0: f64, a1: f64, ccw: bool);
+    /// Ends the current subpath and draws a straight line back to its start.
+    fn close_path(&mut self);
+}
+
+/// `SvgPath` serializes drawing commands as an SVG path data string.
+#[derive(Clone, Debug, Default)]
+pub struct SvgPath {
+    start: Option<(f64, f64)>,
+    current: Option<(f64, f64)>,
+    digits: Option<usize>,
+    data: String,
+}
+
+impl SvgPath {
+    /// Constructs a new, empty path that writes numbers at full precision.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of fractional digits coordinates are rounded to.  If
+    /// `None` coordinates are written at full precision.
+    pub fn digits(self, digits: Option<usize>) -> Self {
+        Self { digits, ..self }
+    }
+
+    fn number(&self, value: f64) -> f64 {
+        let value = match self.digits {
+            Some(digits) => {
+                let k = 10_f64.powi(digits as i32);
+                (value * k).round() / k
+            }
+            None => value,
+        };
+
+        // Don't write negative zero.
+        if value == 0.0 {
+            0.0
+        } else {
+            value
+        }
+    }
+
+    fn append(&mut self, command: char, values: &[f64]) {
+        self.data.push(command);
+        for (i, value) in values.iter().enumerate() {
+            if i > 0 {
+                self.data.push(',');
+            }
+            self.data.push_str(&self.number(*value).to_string());
+        }
+    }
+}
+
+impl PathContext for SvgPath {
+    fn move_to(&mut self, x: f64, y: f64) {
+        self.start = Some((x, y));
+        self.current = Some((x, y));
+        self.append('M', &[x, y]);
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        self.current = Some((x, y));
+        self.append('L', &[x, y]);
+    }
+
+    fn quadratic_curve_to(&mut self, cpx: f64, cpy: f64, x: f64, y: f64) {
+        self.current = Some((x, y));
+        self.append('Q', &[cpx, cpy, x, y]);
+    }
+
+    fn bezier_curve_to(&mut self, cpx1: f64, cpy1: f64, cpx2: f64, cpy2: f64, x: f64, y: f64) {
+        self.current = Some((x, y));
+        self.append('C', &[cpx1, cpy1, cpx2, cpy2, x, y]);
+    }
+
+    fn arc(&mut self, x: f64, y: f64, r: f64, a0: f64, a1: f64, ccw: bool) {
+        // Is the radius negative? Clamp it, drawing no arc.
+        let r = r.max(0.0);
+        let dx = r * a0.cos();
+        let dy = r * a0.sin();
+        let x0 = x + dx;
+        let y0 = y + dy;
+        let cw = if ccw { 0.0 } else { 1.0 };
+        let mut da = if ccw { a0 - a1 } else { a1 - a0 };
+
+        match self.current {
+            // Is this path empty? Move to ⟨x0, y0⟩.
+            None => self.move_to(x0, y0),
+            // Or, is ⟨x0, y0⟩ not coincident with the previous point? Line to ⟨x0, y0⟩.
+            Some((x1, y1)) if (x1 - x0).abs() > EPSILON || (y1 - y0).abs() > EPSILON => {
+                self.line_to(x0, y0)
+            }
+            Some(_) => (),
+        }
+
+        // Is this arc empty? We’re done.
+        if r == 0.0 {
+            return;
+        }
+
+        // Does the angle go the wrong way? Flip the direction.
+        if da < 0.0 {
+            da = da % TAU + TAU;
+        }
+
+        if da > TAU_EPSILON {
+            // Is this a complete circle? Draw two arcs to complete the circle.
+            self.append('A', &[r, r, 0.0, 1.0, cw, x - dx, y - dy]);
+            self.append('A', &[r, r, 0.0, 1.0, cw, x0, y0]);
+            self.current = Some((x0, y0));
+        } else if da > EPSILON {
+            // Is this arc non-empty? Draw an arc!
+            let x1 = x + r * a1.cos();
+            let y1 = y + r * a1.sin();
+            let large_arc = if da >= PI { 1.0 } else { 0.0 };
+
+            self.append('A', &[r, r, 0.0, large_arc, cw, x1, y1]);
+            self.current = Some((x1, y1));
+        }
+    }
+
+    fn close_path(&mut self) {
+        if self.current.is_some() {
+            self.current = self.start;
+            self.data.push('Z');
+        }
+    }
+}
+
+impl fmt::Display for SvgPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.data)
+    }
+}
+
+impl PathContext for Path2D {
+    fn move_to(&mut self, x: f64, y: f64) {
+        Path2D::move_to(self, Vector2F::new(x as f32, y as f32));
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        Path2D::line_to(self, Vector2F::new(x as f32, y as f32));
+    }
+
+    fn quadratic_curve_to(&mut self, cpx: f64, cpy: f64, x: f64, y: f64) {
+        Path2D::quadratic_curve_to(
+            self,
+            Vector2F::new(cpx as f32, cpy as f32),
+            Vector2F::new(x as f32, y as f32),
+        );
+    }
+
+    fn bezier_curve_to(&mut self, cpx1: f64, cpy1: f64, cpx2: f64, cpy2: f64, x: f64, y: f64) {
+        Path2D::bezier_curve_to(
+            self,
+            Vector2F::new(cpx1 as f32, cpy1 as f32),
+            Vector2F::new(cpx2 as f32, cpy2 as f32),
+            Vector2F::new(x as f32, y as f32),
+        );
+    }
+
+    fn arc(&mut self, x: f64, y: f64, r: f64, a0: f64, a1: f64, ccw: bool) {
+        let direction = match ccw {
+            true => ArcDirection::CCW,
+            false => ArcDirection::CW,
+        };
+
+        Path2D::arc(
+            self,
+            Vector2F::new(x as f32, y as f32),
+            r.max(0.0) as f32,
+            a0 as f32,
+            a1 as f32,
+            direction,
+        );
+    }
+
+    fn close_path(&mut self) {
+        Path2D::close_path(self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_path() {
+        // test.strictEqual(path() + "", "");
+        assert_eq!("", SvgPath::new().to_string());
+    }
+
+    #[test]
+    fn move_line_and_close() {
+        let mut p = SvgPath::new();
+
+        // p.moveTo(150, 50); test.pathEqual(p, "M150,50");
+        p.move_to(150.0, 50.0);
+        assert_eq!("M150,50", p.to_string());
+
+        // p.lineTo(200, 100); test.pathEqual(p, "M150,50L200,100");
+        p.line_to(200.0, 100.0);
+        assert_eq!("M150,50L200,100", p.to_string());
+
+        // p.closePath(); test.pathEqual(p, "M150,50L200,100Z");
+        p.close_path();
+        assert_eq!("M150,50L200,100Z", p.to_string());
+    }
+
+    #[test]
+    fn close_path_does_nothing_if_empty() {
+        let mut p = SvgPath::new();
+        p.close_path();
+        assert_eq!("", p.to_string());
+    }
+
+    #[test]
+    fn curves() {
+        let mut p = SvgPath::new();
+        p.move_to(150.0, 50.0);
+
+        // p.quadraticCurveTo(100, 50, 200, 100); test.pathEqual(p, "M150,50Q100,50,200,100");
+        p.quadratic_curve_to(100.0, 50.0, 200.0, 100.0);
+        assert_eq!("M150,50Q100,50,200,100", p.to_string());
+
+        // p.bezierCurveTo(100, 50, 0, 24, 200, 100); test.pathEqual(p, "M150,50Q100,50,200,100C100,50,0,24,200,100");
+        p.bezier_curve_to(100.0, 50.0, 0.0, 24.0, 200.0, 100.0);
+        assert_eq!("M150,50Q100,50,200,100C100,50,0,24,200,100", p.to_string());
+    }
+
+    #[test]
+    fn arc() {
+        {
+            // p.arc(100, 100, 50, 0, Math.PI / 2); test.pathEqual(p, "M150,100A50,50,0,0,1,100,150");
+            let mut p = SvgPath::new().digits(Some(6));
+            p.arc(100.0, 100.0, 50.0, 0.0, PI / 2.0, false);
+            assert_eq!("M150,100A50,50,0,0,1,100,150", p.to_string());
+        }
+
+        {
+            // p.moveTo(100, 100); p.arc(100, 100, 50, 0, Math.PI * 2); test.pathEqual(p, "M100,100L150,100A50,50,0,1,1,50,100A50,50,0,1,1,150,100");
+            let mut p = SvgPath::new().digits(Some(6));
+            p.move_to(100.0, 100.0);
+            p.arc(100.0, 100.0, 50.0, 0.0, TAU, false);
+            assert_eq!(
+                "M100,100L150,100A50,50,0,1,1,50,100A50,50,0,1,1,150,100",
+                p.to_string()
+            );
+        }
+
+        {
+            // p.moveTo(150, 100); p.arc(100, 100, 50, 0, Math.PI * 1, true); test.pathEqual(p, "M150,100A50,50,0,1,0,50,100");
+            let mut p = SvgPath::new().digits(Some(6));
+            p.move_to(150.0, 100.0);
+            p.arc(100.0, 100.0, 50.0, 0.0, PI, true);
+            assert_eq!("M150,100A50,50,0,1,0,50,100", p.to_string());
+        }
+    }
+
+    #[test]
+    fn arc_clamps_a_negative_radius() {
+        let mut p = SvgPath::new();
+        p.move_to(150.0, 100.0);
+        p.arc(100.0, 100.0, -50.0, 0.0, PI, false);
+        assert_eq!("M150,100L100,100", p.to_string());
+
+        let mut p = SvgPath::new();
+        p.arc(100.0, 100.0, std::f64::NAN, 0.0, PI, false);
+        assert_eq!("M100,100", p.to_string());
+    }
+
+    #[test]
+    fn digits_rounds_coordinates() {
+        // const p = pathRound(1); p.moveTo(Math.PI, Math.E); test.strictEqual(p + "", "M3.1,2.7");
+        let mut p = SvgPath::new().digits(Some(1));
+        p.move_to(PI, std::f64::consts::E);
+        assert_eq!("M3.1,2.7", p.to_string());
+
+        let mut p = SvgPath::new().digits(Some(0));
+        p.line_to(-0.1, 2.5);
+        assert_eq!("L0,3", p.to_string());
+    }
+}
@@ -1,3 +1,6 @@
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::hash::Hash;
 use std::ops::Range;
 
 /// Band scales are like [ordinal](crate::scale::ScaleOrdinal) scales except the output range is continuous
@@ -5,25 +8,54 @@ use std::ops::Range;
 /// by dividing the continuous range into uniform bands. Band scales are
 /// typically used for bar charts with an ordinal or categorical dimension.
 #[derive(Clone, Debug)]
-pub struct ScaleBand<'a, DomainType> {
-    pub domain: &'a [DomainType],
+pub struct ScaleBand<'a, DomainType>
+where
+    DomainType: Clone,
+{
+    domain: Cow<'a, [DomainType]>,
     pub range: Range<f64>,
     pub padding_inner: f64,
     pub padding_outer: f64,
     pub align: f64,
+    pub round: bool,
     pub values: Vec<f64>,
     pub band_width: f64,
     pub step: f64,
+    pub unknown: Option<f64>,
+    index: HashMap<DomainType, usize>,
 }
 
 impl<'a, DomainType> ScaleBand<'a, DomainType>
 where
-    DomainType: PartialEq + Copy + Clone,
+    DomainType: Clone + Eq + Hash,
 {
+    /// Sets the domain to the borrowed slice.
     pub fn domain(self, domain: &'a [DomainType]) -> Self {
-        Self { domain, ..self }.recalc()
+        Self {
+            domain: Cow::Borrowed(domain),
+            ..self
+        }
+        .reindex()
+        .recalc()
+    }
+
+    /// Sets the domain, taking ownership of the values.
+    pub fn domain_owned(self, domain: Vec<DomainType>) -> Self {
+        Self {
+            domain: Cow::Owned(domain),
+            ..self
+        }
+        .reindex()
+        .recalc()
     }
 
+    /// Returns the values of the domain.
+    pub fn domain_values(&self) -> &[DomainType] {
+        &self.domain
+    }
+
+    /// Sets the range.  If the range is descending the first band is placed
+    /// at the start of the range, so the bands are in descending order.
     pub fn range<RangeIntermediateType>(self, range: Range<RangeIntermediateType>) -> Self
     where
         RangeIntermediateType: Into<f64>,
@@ -82,18 +114,67 @@ where
         .recalc()
     }
 
+    /// The alignment specifies how any outer padding is distributed: a value
+    /// of 0.5 centers the bands within the range, 0 places the first band at
+    /// the start of the range and 1 places the last band at its end.  The
+    /// alignment is clamped to [0, 1].
+    pub fn align(self, align: f64) -> Self {
+        Self {
+            align: align.clamp(0.0, 1.0),
+            ..self
+        }
+        .recalc()
+    }
+
+    /// If round is enabled, the start of each band, the step and the band
+    /// width will be integers.  Rounding can produce crisp edges, but may
+    /// leave extra space at the ends of the range that is distributed by the
+    /// alignment.
+    pub fn round(self, round: bool) -> Self {
+        Self {
+            round,
+            ..self
+        }
+        .recalc()
+    }
+
+    /// Maps each value in the domain to its first position.
+    fn reindex(self) -> Self {
+        let mut index = HashMap::with_capacity(self.domain.len());
+        for (i, value) in self.domain.iter().enumerate() {
+            index.entry(value.clone()).or_insert(i);
+        }
+
+        Self { index, ..self }
+    }
+
     fn recalc(self) -> Self {
-        let n = self.domain.len();
-        let step = (self.range.end - self.range.start) as f64
-            / 1_f64.max(n as f64 - self.padding_inner + self.padding_outer * 2_f64);
-        let mut start = self.range.start;
-        start +=
-            (self.range.end - self.range.start - (step as f64) * (n as f64 - self.padding_inner))
-                * self.align;
+        let n = self.domain.len() as f64;
+        let reverse = self.range.end < self.range.start;
+        let (start, stop) = match reverse {
+            true => (self.range.end, self.range.start),
+            false => (self.range.start, self.range.end),
+        };
+
+        let mut step = (stop - start)
+            / 1_f64.max(n - self.padding_inner + self.padding_outer * 2_f64);
+        if self.round {
+            step = step.floor();
+        }
 
-        let band_width = step * (1_f64 - self.padding_inner);
+        let mut start = start + (stop - start - step * (n - self.padding_inner)) * self.align;
+        let mut band_width = step * (1_f64 - self.padding_inner);
+        if self.round {
+            start = start.round();
+            band_width = band_width.round();
+        }
 
-        let values = (0..n).map(|x| start + step as f64 * x as f64).collect();
+        let mut values: Vec<f64> = (0..self.domain.len())
+            .map(|x| start + step * x as f64)
+            .collect();
+        if reverse {
+            values.reverse();
+        }
 
         Self {
             band_width,
@@ -103,28 +184,80 @@ where
         }
     }
 
-    pub fn scale(&self, value: DomainType) -> f64 {
-        match self.domain.iter().position(|&x| x == value) {
-            Some(idx) => self.values[(idx) % self.values.len()],
-            None => panic!(),
+    /// Sets the value returned for values that are not in the domain.
+    pub fn unknown(self, unknown: f64) -> Self {
+        Self {
+            unknown: Some(unknown),
+            ..self
+        }
+    }
+
+    /// Returns the start of the band for the given domain value, or the
+    /// unknown value if it is not in the domain.  Returns `None` if it is not
+    /// in the domain and no unknown value is set.
+    pub fn try_scale<Q>(&self, value: &Q) -> Option<f64>
+    where
+        DomainType: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.index.get(value) {
+            Some(&idx) => Some(self.values[idx]),
+            None => self.unknown,
+        }
+    }
+
+    /// Like [try_scale](ScaleBand::try_scale), but panics if the value is not
+    /// in the domain and no unknown value is set.
+    pub fn scale<Q>(&self, value: &Q) -> f64
+    where
+        DomainType: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.try_scale(value) {
+            Some(value) => value,
+            None => panic!("value is not in the domain and no unknown value is set"),
         }
     }
+
+    /// Returns the domain value whose band contains the given position, or
+    /// `None` if the position falls in the padding or outside the range.
+    /// Bands include their start but not their end, except for the last band
+    /// in the range, so an edge shared by adjacent bands maps to the later
+    /// one.  This is useful for interaction, say to determine the band under
+    /// the mouse.
+    pub fn invert<RangeIntermediateType>(&self, position: RangeIntermediateType) -> Option<DomainType>
+    where
+        RangeIntermediateType: Into<f64>,
+    {
+        let position = position.into();
+        let last = self.values.iter().cloned().fold(std::f64::NEG_INFINITY, f64::max);
+
+        self.values.iter()
+            .position(|&start| {
+                let end = start + self.band_width;
+                start <= position && (position < end || (start == last && position == end))
+            })
+            .map(|idx| self.domain[idx].clone())
+    }
 }
 
 impl<'a, DomainType> Default for ScaleBand<'a, DomainType>
 where
-    DomainType: PartialEq + Copy + Clone,
+    DomainType: Clone + Eq + Hash,
 {
     fn default() -> Self {
         Self {
-            domain: &[],
+            domain: Cow::Borrowed(&[]),
             range: 0.0..1.0,
             padding_inner: 0_f64,
             padding_outer: 0_f64,
             align: 0.5,
+            round: false,
             values: vec![],
             band_width: std::f64::NAN,
             step: std::f64::NAN,
+            unknown: None,
+            index: HashMap::new(),
         }.recalc()
     }
 }
@@ -138,15 +271,15 @@ mod tests {
         let scale = ScaleBand::<i32>::new();
 
         let annotation : &[i32] = &[];
-        assert_eq!(annotation, scale.domain);
+        assert_eq!(annotation, scale.domain_values());
         assert_eq!(0.0..1.0, scale.range);
         assert_eq!(1.0, scale.band_width);
         assert_eq!(1.0, scale.step);
         assert_eq!(0.0, scale.padding_inner);
         assert_eq!(0.0, scale.padding_outer);
         assert_eq!(0.5, scale.align);
-
-        //     test.equal(s.round(), false);
+        assert_eq!(None, scale.unknown);
+        assert_eq!(false, scale.round);
     }
 
     #[test]
@@ -260,4 +393,125 @@ mod tests {
         }
 
     }
+
+    #[test]
+    fn unknown_values() {
+        let scale = ScaleBand::<&str>::new()
+            .domain(&["a", "b"])
+            .range(0..100);
+
+        assert_eq!(Some(50.0), scale.try_scale("b"));
+        assert_eq!(None, scale.try_scale("z"));
+
+        let scale = scale.unknown(-1.0);
+
+        assert_eq!(Some(-1.0), scale.try_scale("z"));
+        assert_eq!(-1.0, scale.scale("z"));
+    }
+
+    #[test]
+    fn align_distributes_the_outer_padding() {
+        let scale = ScaleBand::<&str>::new()
+            .domain(&["a", "b", "c"])
+            .range(0..120)
+            .padding_outer(1.0);
+
+        assert_eq!(vec![24.0, 48.0, 72.0], scale.values);
+        assert_eq!(vec![0.0, 24.0, 48.0], scale.clone().align(0.0).values);
+        assert_eq!(vec![48.0, 72.0, 96.0], scale.clone().align(1.0).values);
+        assert_eq!(0.0, scale.align(-1.0).align);
+    }
+
+    #[test]
+    fn round_computes_discrete_rounded_bands() {
+        let scale = ScaleBand::<&str>::new()
+            .domain(&["a", "b", "c"])
+            .range(0..100)
+            .round(true);
+
+        assert_eq!(vec![1.0, 34.0, 67.0], scale.values);
+        assert_eq!(33.0, scale.band_width);
+
+        let scale = scale.padding(0.2);
+
+        assert_eq!(vec![7.0, 38.0, 69.0], scale.values);
+        assert_eq!(25.0, scale.band_width);
+    }
+
+    #[test]
+    fn range_can_be_reversed() {
+        let scale = ScaleBand::<&str>::new()
+            .domain(&["a", "b", "c"])
+            .range(120..0);
+
+        assert_eq!(80.0, scale.scale("a"));
+        assert_eq!(40.0, scale.scale("b"));
+        assert_eq!(0.0, scale.scale("c"));
+        assert_eq!(40.0, scale.band_width);
+
+        let scale = scale.padding(0.2);
+
+        assert_eq!(82.5, scale.scale("a"));
+        assert_eq!(45.0, scale.scale("b"));
+        assert_eq!(7.5, scale.scale("c"));
+        assert_eq!(30.0, scale.band_width);
+    }
+
+    #[test]
+    fn invert_maps_a_position_to_its_band() {
+        let scale = ScaleBand::<&str>::new()
+            .domain(&["a", "b", "c"])
+            .range(0..120)
+            .padding(0.2);
+
+        assert_eq!(Some("a"), scale.invert(7.5));
+        assert_eq!(Some("a"), scale.invert(20));
+        assert_eq!(Some("b"), scale.invert(60));
+        assert_eq!(Some("c"), scale.invert(112.5));
+        assert_eq!(None, scale.invert(40));
+        assert_eq!(None, scale.invert(0));
+        assert_eq!(None, scale.invert(130));
+
+        let scale = scale.range(120..0);
+
+        assert_eq!(Some("a"), scale.invert(100));
+        assert_eq!(Some("c"), scale.invert(20));
+    }
+
+    #[test]
+    fn invert_maps_a_shared_edge_to_the_later_band() {
+        let scale = ScaleBand::<&str>::new()
+            .domain(&["a", "b", "c"])
+            .range(0..90);
+
+        assert_eq!(Some("a"), scale.invert(0));
+        assert_eq!(Some("b"), scale.invert(30));
+        assert_eq!(Some("c"), scale.invert(60));
+        assert_eq!(Some("c"), scale.invert(90));
+        assert_eq!(None, scale.invert(90.5));
+
+        let scale = scale.range(90..0);
+
+        assert_eq!(Some("c"), scale.invert(0));
+        assert_eq!(Some("b"), scale.invert(30));
+        assert_eq!(Some("a"), scale.invert(60));
+        assert_eq!(Some("a"), scale.invert(90));
+    }
+
+    #[test]
+    fn owned_domain_of_strings() {
+        let stations: Vec<String> = ["Berkeley", "Richmond", "Fremont"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+
+        let scale = ScaleBand::new()
+            .domain_owned(stations)
+            .range(0..90);
+
+        assert_eq!(0.0, scale.scale("Berkeley"));
+        assert_eq!(60.0, scale.scale(&"Fremont".to_string()));
+        assert_eq!(None, scale.try_scale("Oakland"));
+        assert_eq!(Some("Richmond".to_string()), scale.invert(45));
+    }
 }
@@ -1,11 +1,14 @@
-use crate::error::Result;
+use crate::error::{BuckyError, Result};
+use crate::interpolate::RangeInterpolator;
 
 use std::ops::Range;
 
 /// Continuous scales map a continuous, quantitative input domain to a
 /// continuous output range. If the range is also numeric, the mapping may be
-/// inverted. The ScaleContinuous trait is implemented by the: [linear](crate::scale::ScaleLinear), power,
-/// [log](crate::scale::ScaleLog), identity, radial, [time](crate::scale::ScaleTime) and sequential color scale structs.
+/// inverted. The ScaleContinuous trait is implemented by the: [linear](crate::scale::ScaleLinear), [power](crate::scale::ScalePow),
+/// [log](crate::scale::ScaleLog), [symlog](crate::scale::ScaleSymlog), [identity](crate::scale::ScaleIdentity), [radial](crate::scale::ScaleRadial), [time](crate::scale::ScaleTime) scale structs. The [sequential](crate::scale::ScaleSequential)
+/// and [diverging](crate::scale::ScaleDiverging) color scales offer the same methods, but map
+/// through an interpolator function rather than a range.
 pub trait ScaleContinuous<'a, DomainType, RangeType>
 where
     Self: Sized,
@@ -32,3 +35,95 @@ where
 
     fn ticks(&self, count: Option<i32>) -> Vec<DomainType>;
 }
+
+/// Maps a value through polylinear stops, as used by the scales that accept
+/// more than two stops in their domain and range.  The transform is applied
+/// to the domain stops and must already have been applied to the value.  The
+/// domain stops may be ascending or descending; values outside the stops are
+/// extrapolated from the first or last segment.
+pub(crate) fn polymap<'a, DomainType, RangeType, InterpolatorType>(
+    domain: &[DomainType],
+    range: &[RangeType],
+    interpolator: &'a InterpolatorType,
+    transform: impl Fn(&DomainType) -> f64,
+    t: f64,
+) -> RangeType
+where
+    RangeType: Clone,
+    InterpolatorType: RangeInterpolator<'a, RangeType>,
+{
+    let n = domain.len().min(range.len());
+    let descending = transform(&domain[n - 1]) < transform(&domain[0]);
+
+    let i = domain[1..n - 1]
+        .iter()
+        .map(&transform)
+        .take_while(|&stop| match descending {
+            true => stop > t,
+            false => stop <= t,
+        })
+        .count();
+
+    let d0 = transform(&domain[i]);
+    let d1 = transform(&domain[i + 1]);
+    let normalized = (t - d0) / (d1 - d0);
+
+    interpolator.interpolate_range(&(range[i].clone()..range[i + 1].clone()), normalized)
+}
+
+/// The inverse of [polymap](polymap): returns the index of the segment of the
+/// range stops containing the value, and the position of the value within
+/// that segment, 0 at its start and 1 at its end.  As with polymap, the
+/// segment is the last one starting at or before the value, so values outside
+/// the stops are placed in the first or last segment.  Flat segments, whose
+/// stops are equal, are skipped; it is an error if every segment is flat.
+pub(crate) fn polyinvert<'a, RangeType, InterpolatorType>(
+    range: &[RangeType],
+    interpolator: &'a InterpolatorType,
+    value: RangeType,
+) -> Result<(usize, f64)>
+where
+    RangeType: Clone,
+    InterpolatorType: RangeInterpolator<'a, RangeType>,
+{
+    let mut segment = None;
+
+    for i in 0..range.len() - 1 {
+        let stops = range[i].clone()..range[i + 1].clone();
+        let t = match interpolator.uninterpolate_range(&stops, value.clone()) {
+            Ok(t) => t,
+            Err(BuckyError::DegenerateRange) => continue,
+            Err(err) => return Err(err),
+        };
+
+        if segment.is_none() || t >= 0.0 {
+            segment = Some((i, t));
+        }
+    }
+
+    segment.ok_or(BuckyError::DegenerateRange)
+}
+
+/// Returns the lower and upper bounds used to clamp values to the domain.
+/// These are the first and last of the domain stops that [polymap](polymap)
+/// uses, so that extra domain stops with no matching range stop are ignored.
+pub(crate) fn clamp_bounds<T>(domain: &[T], range_len: usize) -> (T, T)
+where
+    T: PartialOrd + Copy,
+{
+    let first = domain[0];
+    let last = domain[domain.len().min(range_len) - 1];
+
+    match last < first {
+        true => (last, first),
+        false => (first, last),
+    }
+}
+
+/// Validates polylinear stops, which must number at least two.
+pub(crate) fn check_stops<T>(stops: &[T]) -> Result<()> {
+    match stops.len() {
+        0 | 1 => Err(BuckyError::InsufficientStops),
+        _ => Ok(()),
+    }
+}
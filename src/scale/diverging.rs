@@ -0,0 +1,219 @@
+use std::ops::Range;
+
+use crate::error::Result;
+use crate::scale::ScaleTransform;
+
+/// Diverging scales are similar to [sequential](crate::scale::ScaleSequential)
+/// scales, except the domain has three values: a minimum, a midpoint and a
+/// maximum.  The interpolator is called with 0 at the minimum, 0.5 at the
+/// midpoint and 1 at the maximum, so that ramps such as red-white-green are
+/// centered on the midpoint even if the domain is asymmetric.
+///
+/// Log, power and symlog flavors are available through the respective
+/// constructors or the [transform](ScaleDiverging::transform) method.
+pub struct ScaleDiverging<'a, RangeType> {
+    pub domain: [f64; 3],
+    pub clamped: bool,
+    pub transform: ScaleTransform,
+    interpolator: Box<dyn Fn(f64) -> RangeType + 'a>,
+}
+
+impl<'a, RangeType> ScaleDiverging<'a, RangeType> {
+    /// Constructs a diverging scale with the given interpolator, a domain of
+    /// [0, 0.5, 1] and a linear transform.
+    pub fn new(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self {
+            domain: [0.0, 0.5, 1.0],
+            clamped: false,
+            transform: ScaleTransform::Linear,
+            interpolator,
+        }
+    }
+
+    /// Constructs a diverging scale with a base 10 logarithmic transform and
+    /// a domain of [0.1, 1, 10].
+    pub fn log(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self {
+            domain: [0.1, 1.0, 10.0],
+            transform: ScaleTransform::Log(10.0),
+            ..Self::new(interpolator)
+        }
+    }
+
+    /// Constructs a diverging scale with a power transform with an exponent
+    /// of 1.
+    pub fn pow(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self::new(interpolator).transform(ScaleTransform::Pow(1.0))
+    }
+
+    /// Constructs a diverging scale with a power transform with an exponent
+    /// of 0.5.
+    pub fn sqrt(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self::new(interpolator).transform(ScaleTransform::Pow(0.5))
+    }
+
+    /// Constructs a diverging scale with a symmetric log transform with a
+    /// constant of 1 and a domain of [-1, 0, 1].
+    pub fn symlog(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self {
+            domain: [-1.0, 0.0, 1.0],
+            transform: ScaleTransform::Symlog(1.0),
+            ..Self::new(interpolator)
+        }
+    }
+
+    pub fn interpolator(self, interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self {
+            interpolator,
+            ..self
+        }
+    }
+
+    pub fn transform(self, transform: ScaleTransform) -> Self {
+        Self { transform, ..self }
+    }
+
+    /// Sets the minimum, midpoint and maximum of the domain.
+    pub fn domain<DomainIntermediateType>(self, domain: [DomainIntermediateType; 3]) -> Self
+    where
+        DomainIntermediateType: Into<f64> + Copy,
+    {
+        Self {
+            domain: [domain[0].into(), domain[1].into(), domain[2].into()],
+            ..self
+        }
+    }
+
+    pub fn clamped(self, clamped: bool) -> Self {
+        Self { clamped, ..self }
+    }
+
+    /// Extends the minimum and maximum of the domain to nice round values.
+    /// The midpoint is left unchanged.
+    pub fn nice(self, count: Option<i32>) -> Result<Self> {
+        let extent = self.transform.nice(&self.extent(), count.unwrap_or(10))?;
+
+        Ok(Self {
+            domain: [extent.start, self.domain[1], extent.end],
+            ..self
+        })
+    }
+
+    pub fn scale<DomainIntermediateType>(&self, t: DomainIntermediateType) -> RangeType
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        let t0 = self.transform.apply(self.domain[0]);
+        let t1 = self.transform.apply(self.domain[1]);
+        let t2 = self.transform.apply(self.domain[2]);
+        let k10 = if t0 == t1 { 0.0 } else { 0.5 / (t1 - t0) };
+        let k21 = if t1 == t2 { 0.0 } else { 0.5 / (t2 - t1) };
+        let sign = if t1 < t0 { -1.0 } else { 1.0 };
+
+        let t = self.transform.apply(t.into());
+        let k = if sign * t < sign * t1 { k10 } else { k21 };
+        let normalized = 0.5 + (t - t1) * k;
+
+        let normalized = match self.clamped {
+            true => normalized.clamp(0.0, 1.0),
+            false => normalized,
+        };
+
+        (self.interpolator)(normalized)
+    }
+
+    /// Returns the ticks between the minimum and maximum of the domain.
+    pub fn ticks(&self, count: Option<i32>) -> Vec<f64> {
+        self.transform.ticks(&self.extent(), count.unwrap_or(10))
+    }
+
+    fn extent(&self) -> Range<f64> {
+        self.domain[0]..self.domain[2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScaleDiverging::new(Box::new(|t| t));
+
+        assert_eq!([0.0, 0.5, 1.0], scale.domain);
+        assert_eq!(false, scale.clamped);
+        assert_eq!(ScaleTransform::Linear, scale.transform);
+        assert_eq!(0.25, scale.scale(0.25));
+    }
+
+    #[test]
+    fn midpoint_maps_to_one_half() {
+        let scale = ScaleDiverging::new(Box::new(|t| t)).domain([-1.0, 0.0, 3.0]);
+
+        assert_eq!(0.0, scale.scale(-1));
+        assert_eq!(0.25, scale.scale(-0.5));
+        assert_eq!(0.5, scale.scale(0));
+        assert_eq!(0.75, scale.scale(1.5));
+        assert_eq!(1.0, scale.scale(3));
+        assert_eq!(1.25, scale.scale(4.5));
+
+        let scale = scale.clamped(true);
+
+        assert_eq!(0.0, scale.scale(-2));
+        assert_eq!(1.0, scale.scale(4.5));
+    }
+
+    #[test]
+    fn descending_domain() {
+        let scale = ScaleDiverging::new(Box::new(|t| t)).domain([3.0, 0.0, -1.0]);
+
+        assert_eq!(0.0, scale.scale(3));
+        assert_eq!(0.25, scale.scale(1.5));
+        assert_eq!(0.5, scale.scale(0));
+        assert_eq!(1.0, scale.scale(-1));
+    }
+
+    #[test]
+    fn log_flavor() {
+        let scale = ScaleDiverging::log(Box::new(|t| t));
+
+        assert_eq!([0.1, 1.0, 10.0], scale.domain);
+        assert_delta!(0.0, scale.scale(0.1), DELTA);
+        assert_delta!(0.5, scale.scale(1), DELTA);
+        assert_delta!(0.8494850, scale.scale(5), DELTA);
+    }
+
+    #[test]
+    fn symlog_flavor() {
+        let scale = ScaleDiverging::symlog(Box::new(|t| t)).domain([-100, 0, 1000]);
+
+        assert_delta!(0.0, scale.scale(-100), DELTA);
+        assert_delta!(0.5, scale.scale(0), DELTA);
+        assert_delta!(1.0, scale.scale(1000), DELTA);
+        assert_eq!(
+            vec![-100.0, -10.0, -1.0, 0.0, 1.0, 10.0, 100.0, 1000.0],
+            scale.ticks(None)
+        );
+    }
+
+    #[test]
+    fn sqrt_flavor() {
+        let scale = ScaleDiverging::sqrt(Box::new(|t| t)).domain([-4, 0, 16]);
+
+        assert_delta!(0.25, scale.scale(-1), DELTA);
+        assert_delta!(0.75, scale.scale(4), DELTA);
+    }
+
+    #[test]
+    fn nice_leaves_the_midpoint() -> Result<()> {
+        let scale = ScaleDiverging::new(Box::new(|t| t))
+            .domain([-9.5, 0.3, 98.2])
+            .nice(None)?;
+
+        assert_eq!([-10.0, 0.3, 100.0], scale.domain);
+
+        Ok(())
+    }
+}
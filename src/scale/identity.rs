@@ -0,0 +1,187 @@
+use std::ops::Range;
+
+use crate::array::ticks::Ticks;
+use crate::error::Result;
+use crate::scale::continuous::*;
+use crate::scale::linear::nice_linear;
+
+/// Identity scales are a special case of [linear](crate::scale::ScaleLinear)
+/// scales where the domain and range are identical; the scale is thus the
+/// identity function. These scales are occasionally useful when working with
+/// pixel coordinates, say in conjunction with an axis.
+///
+/// Setting either the domain or the range sets the domain, which is also the
+/// range.  Unlike the other continuous scales the domain may be descending,
+/// since pixel ranges often are.
+#[derive(Clone, Debug)]
+pub struct ScaleIdentity {
+    pub domain: Range<f64>,
+    pub clamped: bool,
+}
+
+impl ScaleIdentity {
+    pub fn new() -> Self {
+        Self {
+            domain: 0.0..1.0,
+            clamped: false,
+        }
+    }
+
+    fn ascending(&self) -> Range<f64> {
+        if self.domain.end < self.domain.start {
+            self.domain.end..self.domain.start
+        } else {
+            self.domain.clone()
+        }
+    }
+}
+
+impl<'a> ScaleContinuous<'a, f64, f64> for ScaleIdentity {
+    fn domain<DomainIntermediateType>(self, domain: Range<DomainIntermediateType>) -> Result<Self>
+    where
+        DomainIntermediateType: Into<f64> + PartialOrd,
+    {
+        Ok(Self {
+            domain: domain.start.into()..domain.end.into(),
+            ..self
+        })
+    }
+
+    fn range<RangeIntermediateType>(self, range: Range<RangeIntermediateType>) -> Result<Self>
+    where
+        RangeIntermediateType: Into<f64>,
+    {
+        Ok(Self {
+            domain: range.start.into()..range.end.into(),
+            ..self
+        })
+    }
+
+    fn clamped(self, clamped: bool) -> Self {
+        Self { clamped, ..self }
+    }
+
+    fn nice<CountType>(self, count: Option<CountType>) -> Result<Self>
+    where
+        CountType: Into<i32>,
+    {
+        let count = match count {
+            Some(count) => count.into(),
+            None => 10,
+        };
+
+        let nice = nice_linear(&self.ascending(), count);
+        let domain = match self.domain.end < self.domain.start {
+            true => nice.end..nice.start,
+            false => nice,
+        };
+
+        Ok(Self { domain, ..self })
+    }
+
+    fn scale<DomainIntermediateType>(&'a self, t: DomainIntermediateType) -> f64
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        let t = t.into();
+        let domain = self.ascending();
+
+        match self.clamped {
+            true => domain.start.max(domain.end.min(t)),
+            false => t,
+        }
+    }
+
+    /// Returns the ticks of the equivalent linear scale, in the order of the
+    /// domain.
+    fn ticks(&self, tick_count: Option<i32>) -> Vec<f64> {
+        let mut ticks = self.ascending().ticks(tick_count);
+
+        if self.domain.end < self.domain.start {
+            ticks.reverse();
+        }
+
+        ticks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScaleIdentity::new();
+
+        assert_eq!(0.0..1.0, scale.domain);
+        assert_eq!(false, scale.clamped);
+    }
+
+    #[test]
+    fn identity_is_the_identity_function() -> Result<()> {
+        let scale = ScaleIdentity::new().domain(1..2)?;
+
+        assert_eq!(0.5, scale.scale(0.5));
+        assert_eq!(1.0, scale.scale(1));
+        assert_eq!(1.5, scale.scale(1.5));
+        assert_eq!(2.0, scale.scale(2));
+        assert_eq!(2.5, scale.scale(2.5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn domain_and_range_are_aliases() -> Result<()> {
+        let scale = ScaleIdentity::new().range(0..960)?;
+        assert_eq!(0.0..960.0, scale.domain);
+
+        let scale = scale.domain(500..0)?;
+        assert_eq!(500.0..0.0, scale.domain);
+
+        Ok(())
+    }
+
+    #[test]
+    fn clamped() -> Result<()> {
+        let scale = ScaleIdentity::new().range(100..0)?.clamped(true);
+
+        assert_eq!(0.0, scale.scale(-10));
+        assert_eq!(50.0, scale.scale(50));
+        assert_eq!(100.0, scale.scale(110));
+
+        Ok(())
+    }
+
+    #[test]
+    fn ticks_follow_the_domain() -> Result<()> {
+        let scale = ScaleIdentity::new();
+        assert_eq!(
+            vec![0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8, 0.9, 1.0],
+            scale.ticks(Some(10))
+        );
+
+        let scale = scale.range(100..0)?;
+        assert_eq!(
+            vec![100.0, 80.0, 60.0, 40.0, 20.0, 0.0],
+            scale.ticks(Some(5))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn nice_extends_the_domain_to_round_numbers() -> Result<()> {
+        let scale = ScaleIdentity::new();
+
+        assert_eq!(
+            1.0..11.0,
+            scale.clone().domain(1.1..10.9)?.nice(None::<i32>)?.domain
+        );
+        assert_eq!(
+            11.0..1.0,
+            scale.clone().domain(10.9..1.1)?.nice(None::<i32>)?.domain
+        );
+
+        Ok(())
+    }
+}
@@ -10,10 +10,15 @@ use crate::scale::continuous::*;
 /// Linear scales are a good default choice for continuous quantitative data
 /// because they preserve proportional differences. Each range value y can be
 /// expressed as a function of the domain value x: y = mx + b.
+///
+/// The domain and range usually have two stops, but may have more to map
+/// each segment of the domain to the corresponding segment of the range, as
+/// with d3's polylinear scales.  For example a domain of [-1, 0, 1] and a
+/// range of red, white and green gives a diverging color ramp.
 #[derive(Clone, Debug)]
 pub struct ScaleLinear<RangeType, InterpolatorType> {
-    pub domain: Range<f64>,
-    pub range: Range<RangeType>,
+    pub domain: Vec<f64>,
+    pub range: Vec<RangeType>,
     pub clamped: bool,
     pub interpolator: InterpolatorType,
 }
@@ -27,14 +32,7 @@ where
     where
         DomainIntermediateType: Into<f64> + PartialOrd,
     {
-        if domain.start > domain.end {
-            return Err(BuckyError::DescendingScale);
-        }
-
-        Ok(Self {
-            domain: domain.start.into()..domain.end.into(),
-            ..self
-        })
+        self.domain_stops(&[domain.start.into(), domain.end.into()])
     }
 
     fn range<RangeIntermediateType>(self, range: Range<RangeIntermediateType>) -> Result<Self>
@@ -42,7 +40,7 @@ where
         RangeIntermediateType: Into<RangeType>,
     {
         Ok(Self {
-            range: range.start.into()..range.end.into(),
+            range: vec![range.start.into(), range.end.into()],
             ..self
         })
     }
@@ -51,40 +49,27 @@ where
         Self { clamped, ..self }
     }
 
+    /// Extends the first and last stops of the domain to nice round values.
+    /// Any stops in between are left unchanged.
     fn nice<CountType>(self, count: Option<CountType>) -> Result<Self>
     where
         CountType: Into<i32>,
     {
-        assert!(self.domain.end > self.domain.start);
+        let extent = self.extent();
 
         let count = match count {
             Some(count) => count.into(),
             None => 10,
         };
 
-        let mut start = self.domain.start;
-        let mut stop = self.domain.end;
-        let mut step = self.domain.tick_increment(count);
-
-        if step > 0.0 {
-            start = (start / step).floor() * step;
-            stop = (stop / step).ceil() * step;
-            step = (start..stop).tick_increment(count);
-        } else if step < 0.0 {
-            start = (start * step).ceil() / step;
-            stop = (stop * step).floor() / step;
-            step = (start..stop).tick_increment(count);
-        }
+        let nice = nice_linear(&extent, count);
 
-        if step > 0.0 {
-            start = (start / step).floor() * step;
-            stop = (stop / step).ceil() * step;
-        } else if step < 0.0 {
-            start = (start * step).ceil() / step;
-            stop = (stop * step).floor() / step;
-        }
+        let mut domain = self.domain;
+        let last = domain.len() - 1;
+        domain[0] = nice.start;
+        domain[last] = nice.end;
 
-        self.domain(start..stop)
+        Ok(Self { domain, ..self })
     }
 
     fn scale<DomainIntermediateType>(&'a self, t: DomainIntermediateType) -> RangeType
@@ -94,37 +79,133 @@ where
     {
         let t = t.into();
 
-        let domain;
-        let range;
-
-        if self.domain.end < self.domain.start {
-            domain = self.domain.end..self.domain.start;
-            range = self.range.end..self.range.start;
-        } else {
-            domain = self.domain.start..self.domain.end;
-            range = self.range.clone();
+        let clamped = match self.clamped {
+            true => {
+                let (min, max) = clamp_bounds(&self.domain, self.range.len());
+                t.clamp(min, max)
+            }
+            false => t,
         };
 
-        let clamped = match self.clamped {
-            true => domain.start.max(domain.end.min(t)),
+        polymap(&self.domain, &self.range, &self.interpolator, |d| *d, clamped)
+    }
+
+    fn ticks(&self, tick_count: Option<i32>) -> Vec<f64> {
+        self.extent().ticks(tick_count)
+    }
+}
+
+impl<'a, RangeType, InterpolatorType> ScaleLinear<RangeType, InterpolatorType>
+where
+    InterpolatorType: RangeInterpolator<'a, RangeType>,
+{
+    /// Sets the domain to the given stops, of which there must be at least
+    /// two, in ascending or descending order.  If there are more stops in the
+    /// domain than in the range the extra stops are ignored, and vice versa.
+    pub fn domain_stops<DomainIntermediateType>(
+        self,
+        domain: &[DomainIntermediateType],
+    ) -> Result<Self>
+    where
+        DomainIntermediateType: Into<f64> + Clone,
+    {
+        let domain: Vec<f64> = domain.iter().cloned().map(|stop| stop.into()).collect();
+
+        check_stops(&domain)?;
+        let ascending = domain.windows(2).all(|pair| pair[0] <= pair[1]);
+        let descending = domain.windows(2).all(|pair| pair[0] >= pair[1]);
+        if !(ascending || descending) {
+            return Err(BuckyError::NonMonotonicDomain);
+        }
+
+        Ok(Self { domain, ..self })
+    }
+
+    /// Sets the range to the given stops, of which there must be at least
+    /// two.
+    pub fn range_stops<RangeIntermediateType>(
+        self,
+        range: &[RangeIntermediateType],
+    ) -> Result<Self>
+    where
+        RangeIntermediateType: Into<RangeType> + Clone,
+    {
+        let range: Vec<RangeType> = range.iter().cloned().map(|stop| stop.into()).collect();
+
+        check_stops(&range)?;
+
+        Ok(Self { range, ..self })
+    }
+
+    /// Given a value from the range, returns the corresponding value from the
+    /// domain.  Inversion is useful for interaction, say to determine the
+    /// data value corresponding to the position of the mouse.  If the scale
+    /// is clamped the returned value is clamped to the domain.  Returns an
+    /// error if the range isn't numeric or is empty.
+    pub fn invert<RangeIntermediateType>(&'a self, value: RangeIntermediateType) -> Result<f64>
+    where
+        RangeIntermediateType: Into<RangeType>,
+        RangeType: Clone,
+    {
+        let n = self.domain.len().min(self.range.len());
+        let (i, t) = polyinvert(&self.range[..n], &self.interpolator, value.into())?;
+        let t = match self.clamped {
+            true => t.clamp(0.0, 1.0),
             false => t,
         };
 
-        let normalized = (clamped - domain.start) / (domain.end - domain.start);
+        Ok(self.domain[i] + t * (self.domain[i + 1] - self.domain[i]))
+    }
 
-        self.interpolator.interpolate_range(&range, normalized)
+    /// Returns the first and last stops of the domain.
+    pub fn extent(&self) -> Range<f64> {
+        self.domain[0]..self.domain[self.domain.len() - 1]
     }
+}
 
-    fn ticks(&self, tick_count: Option<i32>) -> Vec<f64> {
-        self.domain.ticks(tick_count)
+/// Extends the domain so that it starts and ends on nice round values, as
+/// used by the linear scale and the other scales with linear ticks.  A
+/// descending domain stays descending, and an empty domain is unchanged.
+pub(crate) fn nice_linear(domain: &Range<f64>, count: i32) -> Range<f64> {
+    if domain.end < domain.start {
+        let nice = nice_linear(&(domain.end..domain.start), count);
+        return nice.end..nice.start;
+    }
+
+    if domain.start == domain.end {
+        return domain.clone();
+    }
+
+    let mut start = domain.start;
+    let mut stop = domain.end;
+    let mut step = domain.tick_increment(count);
+
+    if step > 0.0 {
+        start = (start / step).floor() * step;
+        stop = (stop / step).ceil() * step;
+        step = (start..stop).tick_increment(count);
+    } else if step < 0.0 {
+        start = (start * step).ceil() / step;
+        stop = (stop * step).floor() / step;
+        step = (start..stop).tick_increment(count);
     }
+
+    if step > 0.0 {
+        start = (start / step).floor() * step;
+        stop = (stop / step).ceil() * step;
+    } else if step < 0.0 {
+        start = (start * step).ceil() / step;
+        stop = (stop * step).floor() / step;
+    }
+
+    start..stop
 }
 
 impl ScaleLinear<f64, NumberInterpolator> {
     pub fn new() -> Self {
         Self {
-            domain: 0.0..1.0,
-            range: 0.0..1.0,
+            domain: vec![0.0, 1.0],
+            range: vec![0.0, 1.0],
             clamped: false,
             interpolator: NumberInterpolator::new(),
         }
@@ -135,16 +216,19 @@ impl ScaleLinear<f64, NumberInterpolator> {
 impl ScaleLinear<Hsl, HslInterpolator> {
     pub fn new() -> Self {
         Self {
-            domain: 0.0..1.0,
-            range: Hsl {
-                hue: 0.0,
-                saturation: 1.0,
-                lightness: 0.5,
-            }..Hsl {
-                hue: 240.0,
-                saturation: 1.0,
-                lightness: 0.5,
-            },
+            domain: vec![0.0, 1.0],
+            range: vec![
+                Hsl {
+                    hue: 0.0,
+                    saturation: 1.0,
+                    lightness: 0.5,
+                },
+                Hsl {
+                    hue: 240.0,
+                    saturation: 1.0,
+                    lightness: 0.5,
+                },
+            ],
             clamped: false,
             interpolator: HslInterpolator::new(),
         }
@@ -183,6 +267,215 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn invert_maps_a_range_value_to_a_domain_value() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new().range(1..2)?;
+        assert_eq!(0.5, scale.invert(1.5)?);
+
+        let scale = scale.domain(-10..0)?.range(0..1)?;
+        assert_eq!(-10.0, scale.invert(0)?);
+        assert_eq!(-5.0, scale.invert(0.5)?);
+        assert_eq!(5.0, scale.invert(1.5)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invert_clamps_to_the_domain() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new().range(10..20)?.clamped(true);
+
+        assert_eq!(1.0, scale.invert(30)?);
+        assert_eq!(0.0, scale.invert(0)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invert_fails_on_an_empty_range() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new().range(5..5)?;
+
+        assert!(matches!(scale.invert(5), Err(BuckyError::DegenerateRange)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn polylinear_domain_maps_each_segment() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new()
+            .domain_stops(&[-1, 0, 1])?
+            .range_stops(&[0, 100, 120])?;
+
+        assert_eq!(0.0, scale.scale(-1));
+        assert_eq!(50.0, scale.scale(-0.5));
+        assert_eq!(100.0, scale.scale(0));
+        assert_eq!(110.0, scale.scale(0.5));
+        assert_eq!(140.0, scale.scale(2));
+        assert_eq!(-50.0, scale.scale(-1.5));
+        assert_eq!(120.0, scale.clamped(true).scale(2));
+
+        let scale = ScaleLinear::<f64, _>::new()
+            .domain_stops(&[1, 2, 4])?
+            .range_stops(&[4, 2, 1])?;
+
+        assert_eq!(3.0, scale.scale(1.5));
+        assert_eq!(1.5, scale.scale(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn polylinear_extra_stops_are_ignored() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new()
+            .domain_stops(&[0, 10, 20, 30])?
+            .range_stops(&[0, 100])?;
+
+        assert_eq!(50.0, scale.scale(5));
+        assert_eq!(150.0, scale.scale(15));
+
+        let scale = scale.clamped(true);
+
+        assert_eq!(100.0, scale.scale(15));
+        assert_eq!(0.0, scale.scale(-5));
+
+        Ok(())
+    }
+
+    #[test]
+    fn polylinear_invert() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new()
+            .domain_stops(&[1, 2, 4])?
+            .range_stops(&[4, 2, 1])?;
+
+        assert_eq!(1.5, scale.invert(3)?);
+        assert_eq!(3.0, scale.invert(1.5)?);
+        assert_eq!(0.5, scale.invert(5)?);
+        assert_eq!(6.0, scale.invert(0)?);
+
+        let scale = scale.clamped(true);
+
+        assert_eq!(1.0, scale.invert(5)?);
+        assert_eq!(4.0, scale.invert(0)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn polylinear_invert_skips_flat_segments() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new()
+            .domain_stops(&[0, 10, 90, 100])?
+            .range_stops(&[0, 50, 50, 100])?;
+
+        assert_eq!(5.0, scale.invert(25)?);
+        assert_eq!(90.0, scale.invert(50)?);
+        assert_eq!(95.0, scale.invert(75)?);
+        assert_eq!(110.0, scale.invert(150)?);
+
+        let scale = ScaleLinear::<f64, _>::new()
+            .domain_stops(&[0, 1, 2])?
+            .range_stops(&[0, 0, 100])?;
+
+        assert_eq!(1.5, scale.invert(50)?);
+
+        let scale = scale.range_stops(&[5, 5, 5])?;
+
+        assert!(matches!(scale.invert(5), Err(BuckyError::DegenerateRange)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn polylinear_nice_and_ticks_use_the_extent() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new()
+            .domain_stops(&[0.9, 1.0, 2.0, 3.0, 10.9])?
+            .range_stops(&[0, 1, 2, 3, 4])?
+            .nice(None::<i32>)?;
+
+        assert_eq!(vec![0.0, 1.0, 2.0, 3.0, 11.0], scale.domain);
+
+        let scale = scale.domain_stops(&[0, 5, 10])?;
+        assert_eq!(vec![0.0, 5.0, 10.0], scale.ticks(Some(2)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn descending_domain() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new().domain(1..0)?;
+
+        assert_eq!(0.75, scale.scale(0.25));
+        assert_eq!(1.5, scale.scale(-0.5));
+        assert_eq!(0.25, scale.invert(0.75)?);
+        assert_eq!(0.0, scale.clamped(true).scale(2));
+
+        let scale = ScaleLinear::<f64, _>::new()
+            .domain(10..0)?
+            .range(0..100)?;
+
+        assert_eq!(vec![10.0, 8.0, 6.0, 4.0, 2.0, 0.0], scale.ticks(Some(5)));
+        assert_eq!(80.0, scale.scale(2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn descending_polylinear_domain() -> Result<()> {
+        let scale = ScaleLinear::<f64, _>::new()
+            .domain_stops(&[4, 2, 1])?
+            .range_stops(&[1, 2, 4])?;
+
+        assert_eq!(3.0, scale.scale(1.5));
+        assert_eq!(1.5, scale.scale(3));
+        assert_eq!(-2.0, scale.scale(10));
+
+        Ok(())
+    }
+
+    #[test]
+    fn nice_extends_the_domain_to_round_numbers() -> Result<()> {
+        let nice = |start: f64, stop: f64| -> Result<Vec<f64>> {
+            Ok(ScaleLinear::<f64, _>::new()
+                .domain(start..stop)?
+                .nice(None::<i32>)?
+                .domain)
+        };
+
+        assert_eq!(vec![1.0, 11.0], nice(1.1, 10.9)?);
+        assert_eq!(vec![11.0, 1.0], nice(10.9, 1.1)?);
+        assert_eq!(vec![0.0, 12.0], nice(0.7, 11.001)?);
+        assert_eq!(vec![130.0, 0.0], nice(123.1, 6.7)?);
+        assert_eq!(vec![0.0, 0.5], nice(0.0, 0.49)?);
+        assert_eq!(vec![0.5, 0.5], nice(0.5, 0.5)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_stops_cannot_be_set() {
+        let scale = ScaleLinear::<f64, _>::new();
+
+        assert!(matches!(
+            scale.clone().domain_stops(&[1]),
+            Err(BuckyError::InsufficientStops)
+        ));
+        assert!(matches!(
+            scale.clone().range_stops::<f64>(&[]),
+            Err(BuckyError::InsufficientStops)
+        ));
+        assert!(matches!(
+            scale.domain_stops(&[0, 2, 1]),
+            Err(BuckyError::NonMonotonicDomain)
+        ));
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn invert_fails_on_a_color_range() {
+        let scale = ScaleLinear::<Hsl, _>::new();
+        let color = scale.scale(0.5);
+
+        assert!(matches!(scale.invert(color), Err(BuckyError::NonNumericRange)));
+    }
+
     #[cfg(feature = "color")]
     #[test]
     fn color() -> Result<()> {
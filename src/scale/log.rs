@@ -18,11 +18,12 @@ use crate::scale::continuous::*;
 /// multiplied by -1. The behavior of the scale is undefined if you pass a
 /// negative value to a log scale with a positive domain or vice versa.
 ///
-/// TODO: Handle negative scales.
+/// As with [linear](crate::scale::ScaleLinear) scales, the domain and range
+/// may have more than two stops.
 #[derive(Clone, Debug)]
 pub struct ScaleLog<RangeType, InterpolatorType> {
-    pub domain: Range<f64>,
-    pub range: Range<RangeType>,
+    pub domain: Vec<f64>,
+    pub range: Vec<RangeType>,
     pub clamped: bool,
     pub base: f64,
     pub interpolator: InterpolatorType,
@@ -68,7 +69,7 @@ where
     /// provides a convenient way of specifying a format whose precision will
     /// be automatically set by the scale. This should probably be gated by a
     /// crate feature.
-    pub fn tick_format(&'a self, count: Option<i32>) -> impl FnMut(&f64) -> String {
+    pub fn tick_format(&'a self, count: Option<i32>) -> impl FnMut(&f64) -> String + 'a {
         let count = match count {
             Some(count) => count,
             None => 10,
@@ -79,7 +80,7 @@ where
         let k = 1_f64.max(self.base * count as f64 / self.ticks(None).len() as f64);
 
         move |d| {
-            let mut i = d / base.powf(d.log(base).round());
+            let mut i = d / self.pow(self.log(*d).round());
             if i * base < base - 0.5 {
                 i *= base;
             }
@@ -94,6 +95,97 @@ where
             }
         }
     }
+
+    /// Sets the domain to the given stops, of which there must be at least
+    /// two.  The stops must be strictly positive or strictly negative, and
+    /// in ascending or descending order.
+    pub fn domain_stops<DomainIntermediateType>(
+        self,
+        domain: &[DomainIntermediateType],
+    ) -> Result<Self>
+    where
+        DomainIntermediateType: Into<f64> + Clone,
+    {
+        let domain: Vec<f64> = domain.iter().cloned().map(|stop| stop.into()).collect();
+
+        check_stops(&domain)?;
+        if !(domain.iter().all(|&stop| stop < 0.0) || domain.iter().all(|&stop| stop > 0.0)) {
+            return Err(BuckyError::DegenerateDomain);
+        }
+
+        Ok(Self { domain, ..self })
+    }
+
+    /// Sets the range to the given stops, of which there must be at least
+    /// two.
+    pub fn range_stops<RangeIntermediateType>(
+        self,
+        range: &[RangeIntermediateType],
+    ) -> Result<Self>
+    where
+        RangeIntermediateType: Into<RangeType> + Clone,
+    {
+        let range: Vec<RangeType> = range.iter().cloned().map(|stop| stop.into()).collect();
+
+        check_stops(&range)?;
+
+        Ok(Self { range, ..self })
+    }
+
+    /// Given a value from the range, returns the corresponding value from the
+    /// domain by undoing the logarithmic transform.  If the scale is clamped
+    /// the returned value is clamped to the domain.  Returns an error if the
+    /// range isn't numeric or is empty.
+    pub fn invert<RangeIntermediateType>(&'a self, value: RangeIntermediateType) -> Result<f64>
+    where
+        RangeIntermediateType: Into<RangeType>,
+        RangeType: Clone,
+    {
+        let n = self.domain.len().min(self.range.len());
+        let (i, t) = polyinvert(&self.range[..n], &self.interpolator, value.into())?;
+        let t = match self.clamped {
+            true => t.clamp(0.0, 1.0),
+            false => t,
+        };
+
+        let domain_start = self.log(self.domain[i]);
+        let domain_end = self.log(self.domain[i + 1]);
+
+        Ok(self.pow(domain_start + t * (domain_end - domain_start)))
+    }
+
+    /// Returns the first and last stops of the domain.
+    pub fn extent(&self) -> Range<f64> {
+        self.domain[0]..self.domain[self.domain.len() - 1]
+    }
+
+    /// The logarithm of x in the scale's base, reflected for negative domains.
+    fn log(&self, x: f64) -> f64 {
+        let log = |x: f64| {
+            if self.base == 10.0 {
+                x.log10()
+            } else if self.base == 2.0 {
+                x.log2()
+            } else if self.base == std::f64::consts::E {
+                x.ln()
+            } else {
+                x.ln() / self.base.ln()
+            }
+        };
+
+        match self.domain[0] < 0.0 {
+            true => -log(-x),
+            false => log(x),
+        }
+    }
+
+    /// The scale's base raised to x, the inverse of [log](ScaleLog::log).
+    fn pow(&self, x: f64) -> f64 {
+        match self.domain[0] < 0.0 {
+            true => -self.base.powf(-x),
+            false => self.base.powf(x),
+        }
+    }
 }
 
 impl<'a, RangeType, DefaultInterpolator> ScaleContinuous<'a, f64, RangeType>
@@ -105,13 +197,7 @@ where
     where
         DomainIntermediateType: Into<f64> + PartialOrd,
     {
-        let domain = domain.start.into()..domain.end.into();
-
-        if !((domain.end < 0.0 && domain.start < 0.0) || (domain.end > 0.0 && domain.start > 0.0)) {
-            return Err(BuckyError::DegenerateDomain);
-        }
-
-        Ok(Self { domain, ..self })
+        self.domain_stops(&[domain.start.into(), domain.end.into()])
     }
 
     fn range<RangeIntermediateType>(self, range: Range<RangeIntermediateType>) -> Result<Self>
@@ -119,7 +205,7 @@ where
         RangeIntermediateType: Into<RangeType>,
     {
         Ok(Self {
-            range: range.start.into()..range.end.into(),
+            range: vec![range.start.into(), range.end.into()],
             ..self
         })
     }
@@ -128,11 +214,27 @@ where
         Self { clamped, ..self }
     }
 
+    /// Extends the first and last stops of the domain to integer powers of
+    /// the base.  Any stops in between are left unchanged, and the count is
+    /// ignored.
     fn nice<CountType>(self, _count: Option<CountType>) -> Result<Self>
     where
         CountType: Into<i32>,
     {
-        unimplemented!()
+        let last = self.domain.len() - 1;
+        let (i0, i1) = match self.domain[last] < self.domain[0] {
+            true => (last, 0),
+            false => (0, last),
+        };
+
+        let start = self.pow(self.log(self.domain[i0]).floor());
+        let end = self.pow(self.log(self.domain[i1]).ceil());
+
+        let mut domain = self.domain;
+        domain[i0] = start;
+        domain[i1] = end;
+
+        Ok(Self { domain, ..self })
     }
 
     fn scale<DomainIntermediateType>(&'a self, t: DomainIntermediateType) -> RangeType
@@ -142,38 +244,21 @@ where
     {
         let t = t.into();
 
-        if self.domain.end > self.domain.start {
-            let domain = self.domain.start..self.domain.end;
-
-            let clamped = match self.clamped {
-                true => domain.start.max(domain.end.min(t)),
-                false => t,
-            };
-
-            let domain_start = domain.start.ln();
-            let domain_end = domain.end.ln();
-            let clamped = clamped.ln();
-
-            let normalized = (clamped - domain_start) / (domain_end - domain_start);
-
-            return self.interpolator.interpolate_range(&self.range, normalized);
-        } else {
-            let domain = self.domain.end..self.domain.start;
-
-            let clamped = match self.clamped {
-                true => domain.start.max(domain.end.min(t)),
-                false => t,
-            };
-
-            let domain_start = domain.start.ln();
-            let domain_end = domain.end.ln();
-            let clamped = clamped.ln();
-
-            let normalized = (clamped - domain_start) / (domain_end - domain_start);
+        let clamped = match self.clamped {
+            true => {
+                let (min, max) = clamp_bounds(&self.domain, self.range.len());
+                t.clamp(min, max)
+            }
+            false => t,
+        };
 
-            let range = self.range.end..self.range.start;
-            self.interpolator.interpolate_range(&range, normalized)
-        }
+        polymap(
+            &self.domain,
+            &self.range,
+            &self.interpolator,
+            |d| self.log(*d),
+            self.log(clamped),
+        )
     }
 
     /// Like ticks for LinearScale, but customized for a log scale. If the base
@@ -184,23 +269,15 @@ where
     /// count, then at most one tick per power is returned. Otherwise, the tick
     /// values are unfiltered.  If count is not specified, it defaults to 10.
     fn ticks(&self, tick_count: Option<i32>) -> Vec<f64> {
-        let domain = if self.domain.end < self.domain.start {
-            self.domain.end..self.domain.start
+        let extent = self.extent();
+        let domain = if extent.end < extent.start {
+            extent.end..extent.start
         } else {
-            self.domain.start..self.domain.end
+            extent.start..extent.end
         };
 
-        let (log_d0, log_d1) = if domain.start < 0.0 {
-            (
-                -((-domain.start).log(self.base)),
-                -((-domain.end).log(self.base)),
-            )
-        } else {
-            (
-                domain.start.log(self.base),
-                domain.end.log(self.base),
-            )
-        };
+        let log_d0 = self.log(domain.start);
+        let log_d1 = self.log(domain.end);
 
         let n = match tick_count {
             Some(n) => n,
@@ -209,73 +286,69 @@ where
 
         let mut z: Vec<f64> = vec![];
 
-        if self.base % 1.0 == 0.0 {
+        if self.base % 1.0 == 0.0 && (log_d1 - log_d0) < n as f64 {
             let base = self.base.floor() as i32;
 
-            if (log_d1 - log_d0) < n as f64 {
-                let log_d0 = log_d0.floor() as i32;
-                let log_d1 = log_d1.ceil() as i32;
-
-                if domain.start > 0.0 {
-                    for i in log_d0..=log_d1 {
-                        let p = self.base.powi(i);
-                        for k in 1..base {
-                            let t = p * k as f64;
-                            if t < domain.start {
-                                continue;
-                            }
-                            if t > domain.end {
-                                break;
-                            }
-                            z.push(t);
+            let log_d0 = log_d0.floor() as i32;
+            let log_d1 = log_d1.ceil() as i32;
+
+            if domain.start > 0.0 {
+                for i in log_d0..=log_d1 {
+                    let p = self.base.powi(i);
+                    for k in 1..base {
+                        let t = p * k as f64;
+                        if t < domain.start {
+                            continue;
                         }
-                    }
-                } else {
-                    for i in log_d0..=log_d1 {
-                        let p = -(self.base.powi(-i as i32));
-                        for k in (1..=(base - 1)).rev() {
-                            let t = p * (k as f64);
-                            if t < domain.start {
-                                continue;
-                            }
-
-                            if t > domain.end {
-                                break;
-                            }
-
-                            z.push(t);
+                        if t > domain.end {
+                            break;
                         }
+                        z.push(t);
                     }
                 }
+            } else {
+                for i in log_d0..=log_d1 {
+                    let p = -(self.base.powi(-i as i32));
+                    for k in (1..=(base - 1)).rev() {
+                        let t = p * (k as f64);
+                        if t < domain.start {
+                            continue;
+                        }
 
-                if (z.len() * 2) < n as usize {
-                    z = domain.ticks(Some(n));
-                }
+                        if t > domain.end {
+                            break;
+                        }
 
-                if domain.start == self.domain.end {
-                    z.reverse();
+                        z.push(t);
+                    }
                 }
+            }
 
-                return z;
+            if (z.len() * 2) < n as usize {
+                z = domain.ticks(Some(n));
             }
         } else {
             let tick_count = (log_d1 - log_d0).min(n as f64).floor();
-            return (log_d0..log_d1)
+            z = (log_d0..log_d1)
                 .ticks(Some(tick_count as i32))
                 .iter()
-                .map(|n| self.base.powf(*n))
+                .map(|n| self.pow(*n))
                 .collect();
         }
 
-        unimplemented!();
+        if domain.start == extent.end {
+            z.reverse();
+        }
+
+        z
     }
 }
 
 impl<'a> ScaleLog<f64, NumberInterpolator> {
     pub fn new() -> Self {
         Self {
-            domain: 1.0..10.0,
-            range: 0.0..1.0,
+            domain: vec![1.0, 10.0],
+            range: vec![0.0, 1.0],
             clamped: false,
             base: 10.0,
             interpolator: NumberInterpolator::new(),
@@ -297,15 +370,55 @@ mod tests {
     fn expected_defaults() -> Result<()> {
         let scale = ScaleLog::new();
 
-        assert_eq!(1.0..10.0, scale.domain);
-        assert_eq!(0.0..1.0, scale.range);
+        assert_eq!(vec![1.0, 10.0], scale.domain);
+        assert_eq!(vec![0.0, 1.0], scale.range);
         assert_delta!(0.69897, scale.scale(5), DELTA);
         assert_delta!(0.5, scale.scale(3.162278), DELTA);
 
         Ok(())
     }
 
-    // tape("log.domain(…) can take negative values", function(test) {
+    #[test]
+    fn negative_domain_is_reflected() -> Result<()> {
+        let scale = ScaleLog::new().domain(-100..-1)?;
+
+        assert_delta!(0.0, scale.scale(-100), DELTA);
+        assert_delta!(0.150515, scale.scale(-50), DELTA);
+        assert_delta!(1.0, scale.scale(-1), DELTA);
+        assert_delta!(1.5, scale.scale(-0.1), DELTA);
+        assert_delta!(-50.0, scale.invert(0.150515)?, DELTA);
+        assert_delta!(-0.1, scale.invert(1.5)?, DELTA);
+
+        let labels: Vec<_> = scale
+            .ticks(None)
+            .iter()
+            .map(scale.tick_format(None))
+            .collect();
+        assert_eq!(
+            vec![
+                "-1e2", "", "", "", "", "-5e1", "-4e1", "-3e1", "-2e1", "-1e1", "", "", "", "",
+                "-5e0", "-4e0", "-3e0", "-2e0", "-1e0"
+            ],
+            labels
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn negative_domain_clamps_and_inverts() -> Result<()> {
+        let scale = ScaleLog::new().domain(-1..-10)?.clamped(true);
+
+        assert_delta!(0.0, scale.scale(-0.5), DELTA);
+        assert_delta!(0.69897, scale.scale(-5), DELTA);
+        assert_delta!(1.0, scale.scale(-15), DELTA);
+        assert_delta!(-1.0, scale.invert(-1)?, DELTA);
+        assert_delta!(-5.0, scale.invert(0.69897)?, DELTA);
+        assert_delta!(-10.0, scale.invert(2)?, DELTA);
+
+        Ok(())
+    }
+
     // tape("log.domain(…) preserves specified domain exactly, with no floating point error", function(test) {
     // tape("log.interpolate(f) sets the interpolator", function(test) {
 
@@ -412,8 +525,127 @@ mod tests {
         Ok(())
     }
 
-    // tape("log.nice() nices the domain, extending it to powers of ten", function(test) {
-    // tape("log.nice() on a polylog domain only affects the extent", function(test) {
+    #[test]
+    fn invert_maps_y_to_x() -> Result<()> {
+        let scale = ScaleLog::new().domain(1..2)?;
+
+        assert_delta!(0.5, scale.invert(-1.0000000)?, DELTA);
+        assert_delta!(1.0, scale.invert(0.0000000)?, DELTA);
+        assert_delta!(1.5, scale.invert(0.5849625)?, DELTA);
+        assert_delta!(2.0, scale.invert(1.0000000)?, DELTA);
+        assert_delta!(2.5, scale.invert(1.3219281)?, DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invert_clamps_to_the_domain() -> Result<()> {
+        let scale = ScaleLog::new().clamped(true);
+
+        assert_delta!(1.0, scale.invert(-1)?, DELTA);
+        assert_delta!(5.0, scale.invert(0.69897)?, DELTA);
+        assert_delta!(10.0, scale.invert(2)?, DELTA);
+
+        let scale = scale.domain(10..1)?;
+
+        assert_delta!(10.0, scale.invert(-1)?, DELTA);
+        assert_delta!(5.0, scale.invert(0.30103)?, DELTA);
+        assert_delta!(1.0, scale.invert(2)?, DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn polylog_domain_maps_each_segment() -> Result<()> {
+        let scale = ScaleLog::new()
+            .domain_stops(&[0.1, 1.0, 100.0])?
+            .range_stops(&[0, 1, 3])?;
+
+        assert_delta!(0.0, scale.scale(0.1), DELTA);
+        assert_delta!(0.69897, scale.scale(0.5), DELTA);
+        assert_delta!(1.0, scale.scale(1), DELTA);
+        assert_delta!(2.0, scale.scale(10), DELTA);
+        assert_delta!(3.0, scale.scale(100), DELTA);
+        assert_delta!(10.0, scale.invert(2)?, DELTA);
+        assert_delta!(0.5, scale.invert(0.69897)?, DELTA);
+
+        let clamped = scale.clone().range_stops(&[0, 1])?.clamped(true);
+        assert_delta!(1.0, clamped.scale(50), DELTA);
+
+        assert!(scale.clone().domain_stops(&[0.1, -1.0, 10.0]).is_err());
+        assert!(scale.domain_stops(&[1.0]).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn nice_extends_the_domain_to_powers_of_ten() -> Result<()> {
+        let scale = ScaleLog::new().domain(1.1..10.9)?.nice(None::<i32>)?;
+        assert_eq!(vec![1.0, 100.0], scale.domain);
+
+        let scale = scale.domain(10.9..1.1)?.nice(None::<i32>)?;
+        assert_eq!(vec![100.0, 1.0], scale.domain);
+
+        let scale = scale.domain(0.7..11.001)?.nice(None::<i32>)?;
+        assert_eq!(vec![0.1, 100.0], scale.domain);
+
+        let scale = scale.domain(123.1..6.7)?.nice(None::<i32>)?;
+        assert_eq!(vec![1000.0, 1.0], scale.domain);
+
+        let scale = scale.domain(0.01..0.49)?.nice(None::<i32>)?;
+        assert_eq!(vec![0.01, 1.0], scale.domain);
+
+        let scale = scale.domain(1.5..50.0)?.nice(None::<i32>)?;
+        assert_eq!(vec![1.0, 100.0], scale.domain);
+        assert_eq!(0.0, scale.scale(1));
+        assert_eq!(1.0, scale.scale(100));
+
+        Ok(())
+    }
+
+    #[test]
+    fn nice_on_a_polylog_domain_only_affects_the_extent() -> Result<()> {
+        let scale = ScaleLog::new()
+            .domain_stops(&[1.1, 1.5, 10.9])?
+            .nice(None::<i32>)?;
+        assert_eq!(vec![1.0, 1.5, 100.0], scale.domain);
+
+        let scale = scale
+            .domain_stops(&[-123.1, -1.5, -0.5])?
+            .nice(None::<i32>)?;
+        assert_eq!(vec![-1000.0, -1.5, -0.1], scale.domain);
+
+        Ok(())
+    }
+
+    #[test]
+    fn nice_reflects_negative_domains() -> Result<()> {
+        let scale = ScaleLog::new().domain(-1.1..-10.9)?.nice(None::<i32>)?;
+        assert_eq!(vec![-1.0, -100.0], scale.domain);
+
+        let scale = scale.domain(-0.49..-0.01)?.nice(None::<i32>)?;
+        assert_eq!(vec![-1.0, -0.01], scale.domain);
+
+        Ok(())
+    }
+
+    #[test]
+    fn nice_uses_powers_of_the_base() -> Result<()> {
+        let scale = ScaleLog::new()
+            .base(2.0)
+            .domain(3..20)?
+            .nice(None::<i32>)?;
+        assert_eq!(vec![2.0, 32.0], scale.domain);
+
+        let scale = scale
+            .base(std::f64::consts::E)
+            .domain(1.1..10.9)?
+            .nice(None::<i32>)?;
+        assert_delta!(1.0, scale.domain[0], DELTA);
+        assert_delta!(20.0855369, scale.domain[1], DELTA);
+
+        Ok(())
+    }
 
     #[test]
     fn ticks_generates_expected_power_of_ten_ascending() -> Result<()> {
@@ -471,6 +703,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn ticks_on_wide_domains_are_one_per_power() -> Result<()> {
+        let expected: Vec<f64> = (0..=10).map(|i| 10_f64.powi(2 * i)).collect();
+
+        let scale = ScaleLog::new().domain(1.0..1e20)?;
+        assert_eq!(expected, scale.ticks(None));
+
+        let scale = scale.domain(-1.0..-1e20)?;
+        let expected: Vec<f64> = expected.iter().map(|t| -t).collect();
+        assert_eq!(expected, scale.ticks(None));
+
+        Ok(())
+    }
+
     #[test]
     fn ticks_generates_expected_power_of_ten_small_domains() -> Result<()> {
         let scale = ScaleLog::new();
@@ -617,8 +863,8 @@ mod tests {
             .unwrap();
 
         assert_eq!(10.0, scale.base);
-        assert_eq!(78.2..636.23, scale.domain);
-        assert_eq!(550.0..10.0, scale.range);
+        assert_eq!(vec![78.2, 636.23], scale.domain);
+        assert_eq!(vec![550.0, 10.0], scale.range);
 
         assert_eq!(547.0, scale.scale(79.0));
         assert_eq!(1080.0, scale.scale(10.0));
@@ -1,17 +1,35 @@
 mod band;
 mod continuous;
+mod diverging;
+mod identity;
 mod linear;
 mod log;
 mod ordinal;
+mod point;
+mod pow;
 mod quantile;
+mod quantize;
+mod radial;
+mod sequential;
+mod symlog;
+mod threshold;
 #[cfg(feature = "time")]
 mod time;
 
 pub use band::*;
 pub use continuous::*;
+pub use diverging::*;
+pub use identity::*;
 pub use linear::*;
 pub use log::*;
 pub use ordinal::*;
+pub use point::*;
+pub use pow::*;
 pub use quantile::*;
+pub use quantize::*;
+pub use radial::*;
+pub use sequential::*;
+pub use symlog::*;
+pub use threshold::*;
 #[cfg(feature = "time")]
 pub use time::*;
@@ -1,17 +1,31 @@
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::hash::Hash;
+
 /// Unlike [continuous](crate::scale::ScaleContinuous) scales, ordinal scales have a discrete domain and range.
 /// For example, an ordinal scale might map a set of named categories to a set
 /// of colors, or determine the horizontal positions of columns in a column
 /// chart.
+///
+/// The domain may be borrowed or owned, so a scale built from categories
+/// computed on the fly can outlive them.  Values that are not in the domain
+/// map to the unknown value, if one is set.  Alternatively
+/// [scale_implicit](ScaleOrdinal::scale_implicit) grows the domain as d3's
+/// implicit ordinal scales do.
 #[derive(Clone, Debug)]
-pub struct ScaleOrdinal<'a, DomainType, RangeType> {
-    pub domain: &'a [DomainType],
+pub struct ScaleOrdinal<'a, DomainType, RangeType>
+where
+    DomainType: Clone,
+{
+    domain: Cow<'a, [DomainType]>,
     pub range: &'a [RangeType],
+    pub unknown: Option<&'a RangeType>,
+    index: HashMap<DomainType, usize>,
 }
 
 impl<'a, DomainType, RangeType> ScaleOrdinal<'a, DomainType, RangeType>
 where
-    DomainType: PartialEq + Copy,
-    RangeType: PartialEq + Copy,
+    DomainType: Clone + Eq + Hash,
 {
     pub fn new() -> Self {
         Self {
@@ -19,27 +33,204 @@ where
         }
     }
 
+    /// Sets the domain to the borrowed slice.
     pub fn domain(self, domain: &'a [DomainType]) -> Self {
-        Self { domain, ..self }
+        Self {
+            domain: Cow::Borrowed(domain),
+            ..self
+        }
+        .reindex()
+    }
+
+    /// Sets the domain, taking ownership of the values.
+    pub fn domain_owned(self, domain: Vec<DomainType>) -> Self {
+        Self {
+            domain: Cow::Owned(domain),
+            ..self
+        }
+        .reindex()
+    }
+
+    /// Returns the values of the domain.
+    pub fn domain_values(&self) -> &[DomainType] {
+        &self.domain
     }
 
     pub fn range(self, range: &'a [RangeType]) -> Self {
         Self { range, ..self }
     }
 
-    pub fn scale(&self, value: DomainType) -> RangeType {
-        match self.domain.iter().position(|&x| x == value) {
-            Some(idx) => self.range[(idx) % self.range.len()],
-            None => panic!(),
+    /// Sets the value returned for values that are not in the domain.
+    pub fn unknown(self, unknown: &'a RangeType) -> Self {
+        Self {
+            unknown: Some(unknown),
+            ..self
+        }
+    }
+
+    /// Maps each value in the domain to its first position.
+    fn reindex(self) -> Self {
+        let mut index = HashMap::with_capacity(self.domain.len());
+        for (i, value) in self.domain.iter().enumerate() {
+            index.entry(value.clone()).or_insert(i);
         }
+
+        Self { index, ..self }
+    }
+
+    /// Returns the range value for the given domain value, or the unknown
+    /// value if it is not in the domain.  Returns `None` if it is not in the
+    /// domain and no unknown value is set.
+    pub fn try_scale<Q>(&self, value: &Q) -> Option<&'a RangeType>
+    where
+        DomainType: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.index.get(value) {
+            Some(&idx) if !self.range.is_empty() => {
+                Some(&self.range[idx % self.range.len()])
+            }
+            _ => self.unknown,
+        }
+    }
+
+    /// Like [try_scale](ScaleOrdinal::try_scale), but panics if the value is
+    /// not in the domain and no unknown value is set.
+    pub fn scale<Q>(&self, value: &Q) -> &'a RangeType
+    where
+        DomainType: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.try_scale(value) {
+            Some(value) => value,
+            None => panic!("value is not in the domain and no unknown value is set"),
+        }
+    }
+
+    /// Returns the range value for the given domain value.  If the value is
+    /// not in the domain it is appended to it first, so that it maps to the
+    /// next range value, wrapping around when the range is exhausted.  This
+    /// is the implicit domain of d3's ordinal scales, useful when the
+    /// categories aren't known ahead of time.  Returns the unknown value (or
+    /// `None`) only if the range is empty.
+    pub fn scale_implicit(&mut self, value: DomainType) -> Option<&'a RangeType> {
+        if !self.index.contains_key(&value) {
+            self.index.insert(value.clone(), self.domain.len());
+            self.domain.to_mut().push(value.clone());
+        }
+
+        self.try_scale(&value)
     }
 }
 
-impl<'a, DomainType, RangeType> Default for ScaleOrdinal<'a, DomainType, RangeType> {
+impl<'a, DomainType, RangeType> Default for ScaleOrdinal<'a, DomainType, RangeType>
+where
+    DomainType: Clone,
+{
     fn default() -> Self {
         Self {
-            domain: &[],
+            domain: Cow::Borrowed(&[]),
             range: &[],
+            unknown: None,
+            index: HashMap::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScaleOrdinal::<&str, i32>::new();
+
+        assert!(scale.domain_values().is_empty());
+        assert!(scale.range.is_empty());
+        assert_eq!(None, scale.unknown);
+    }
+
+    #[test]
+    fn range_is_cycled() {
+        let scale = ScaleOrdinal::new().domain(&["a", "b", "c"]).range(&[1, 2]);
+
+        assert_eq!(&1, scale.scale("a"));
+        assert_eq!(&2, scale.scale("b"));
+        assert_eq!(&1, scale.scale("c"));
+    }
+
+    #[test]
+    fn duplicate_values_use_the_first_position() {
+        let scale = ScaleOrdinal::new()
+            .domain(&["a", "b", "a"])
+            .range(&[1, 2, 3]);
+
+        assert_eq!(&1, scale.scale("a"));
+        assert_eq!(&2, scale.scale("b"));
+    }
+
+    #[test]
+    fn unknown_values() {
+        let scale = ScaleOrdinal::new().domain(&["a", "b"]).range(&[1, 2]);
+
+        assert_eq!(Some(&1), scale.try_scale("a"));
+        assert_eq!(None, scale.try_scale("z"));
+
+        let scale = scale.unknown(&0);
+
+        assert_eq!(Some(&0), scale.try_scale("z"));
+        assert_eq!(&0, scale.scale("z"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_panics_without_unknown() {
+        let scale = ScaleOrdinal::new().domain(&["a"]).range(&[1]);
+
+        scale.scale("z");
+    }
+
+    #[test]
+    fn empty_range_returns_unknown() {
+        let scale = ScaleOrdinal::<_, i32>::new().domain(&["a"]);
+
+        assert_eq!(None, scale.try_scale("a"));
+        assert_eq!(Some(&-1), scale.unknown(&-1).try_scale("a"));
+    }
+
+    #[test]
+    fn implicit_domain_grows() {
+        let mut scale = ScaleOrdinal::new().range(&["red", "green", "blue"]);
+
+        assert_eq!(Some(&"red"), scale.scale_implicit(10));
+        assert_eq!(Some(&"green"), scale.scale_implicit(20));
+        assert_eq!(Some(&"red"), scale.scale_implicit(10));
+        assert_eq!(Some(&"blue"), scale.scale_implicit(30));
+        assert_eq!(Some(&"red"), scale.scale_implicit(40));
+        assert_eq!(&[10, 20, 30, 40], scale.domain_values());
+
+        let domain = &[1, 2];
+        let mut scale = scale.domain(domain);
+
+        assert_eq!(Some(&"blue"), scale.scale_implicit(3));
+        assert_eq!(&[1, 2], domain);
+        assert_eq!(&[1, 2, 3], scale.domain_values());
+    }
+
+    #[test]
+    fn owned_domain_outlives_its_source() {
+        fn categories() -> ScaleOrdinal<'static, String, &'static str> {
+            let names = vec!["north".to_string(), "south".to_string()];
+
+            ScaleOrdinal::new()
+                .domain_owned(names)
+                .range(&["blue", "red"])
+        }
+
+        let scale = categories();
+
+        assert_eq!(&"blue", scale.scale("north"));
+        assert_eq!(&"red", scale.scale(&"south".to_string()));
+        assert_eq!(None, scale.try_scale("east"));
+    }
+}
@@ -0,0 +1,300 @@
+use std::borrow::{Borrow, Cow};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Range;
+
+/// Point scales are a variant of [band](crate::scale::ScaleBand) scales with
+/// the bandwidth fixed to zero. Point scales are typically used for scatterplots
+/// or line charts with an ordinal or categorical dimension.
+#[derive(Clone, Debug)]
+pub struct ScalePoint<'a, DomainType>
+where
+    DomainType: Clone,
+{
+    domain: Cow<'a, [DomainType]>,
+    pub range: Range<f64>,
+    pub padding: f64,
+    pub align: f64,
+    pub round: bool,
+    pub values: Vec<f64>,
+    pub step: f64,
+    pub unknown: Option<f64>,
+    index: HashMap<DomainType, usize>,
+}
+
+impl<'a, DomainType> ScalePoint<'a, DomainType>
+where
+    DomainType: Clone + Eq + Hash,
+{
+    pub fn new() -> Self {
+        Self {
+            ..Default::default()
+        }
+    }
+
+    /// Sets the domain to the borrowed slice.
+    pub fn domain(self, domain: &'a [DomainType]) -> Self {
+        Self {
+            domain: Cow::Borrowed(domain),
+            ..self
+        }
+        .reindex()
+        .recalc()
+    }
+
+    /// Sets the domain, taking ownership of the values.
+    pub fn domain_owned(self, domain: Vec<DomainType>) -> Self {
+        Self {
+            domain: Cow::Owned(domain),
+            ..self
+        }
+        .reindex()
+        .recalc()
+    }
+
+    /// Returns the values of the domain.
+    pub fn domain_values(&self) -> &[DomainType] {
+        &self.domain
+    }
+
+    /// Sets the range.  If the range is descending the first point is placed
+    /// at the start of the range, so the points are in descending order.
+    pub fn range<RangeIntermediateType>(self, range: Range<RangeIntermediateType>) -> Self
+    where
+        RangeIntermediateType: Into<f64>,
+    {
+        Self {
+            range: range.start.into()..range.end.into(),
+            ..self
+        }
+        .recalc()
+    }
+
+    /// The padding specifies the amount of blank space, in terms of multiples
+    /// of the step, to reserve before the first point and after the last
+    /// point.
+    pub fn padding(self, padding: f64) -> Self {
+        Self { padding, ..self }.recalc()
+    }
+
+    /// The alignment specifies how any outer padding is distributed: a value
+    /// of 0.5 centers the points within the range, 0 places the first point
+    /// at the start of the range and 1 places the last point at its end.  The
+    /// alignment is clamped to [0, 1].
+    pub fn align(self, align: f64) -> Self {
+        Self {
+            align: align.clamp(0.0, 1.0),
+            ..self
+        }
+        .recalc()
+    }
+
+    /// If round is enabled, the position of each point and the step will be
+    /// integers.  Rounding can produce crisp edges, but may leave extra
+    /// space at the ends of the range that is distributed by the alignment.
+    pub fn round(self, round: bool) -> Self {
+        Self { round, ..self }.recalc()
+    }
+
+    /// Returns the distance between adjacent points.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Returns zero, as points have no width.
+    pub fn band_width(&self) -> f64 {
+        0.0
+    }
+
+    /// Maps each value in the domain to its first position.
+    fn reindex(self) -> Self {
+        let mut index = HashMap::with_capacity(self.domain.len());
+        for (i, value) in self.domain.iter().enumerate() {
+            index.entry(value.clone()).or_insert(i);
+        }
+
+        Self { index, ..self }
+    }
+
+    fn recalc(self) -> Self {
+        let n = self.domain.len() as f64;
+        let reverse = self.range.end < self.range.start;
+        let (start, stop) = match reverse {
+            true => (self.range.end, self.range.start),
+            false => (self.range.start, self.range.end),
+        };
+
+        let mut step = (stop - start) / 1_f64.max(n - 1.0 + self.padding * 2.0);
+        if self.round {
+            step = step.floor();
+        }
+
+        let mut start = start + (stop - start - step * (n - 1.0)) * self.align;
+        if self.round {
+            start = start.round();
+        }
+
+        let mut values: Vec<f64> = (0..self.domain.len())
+            .map(|i| start + step * i as f64)
+            .collect();
+        if reverse {
+            values.reverse();
+        }
+
+        Self {
+            step,
+            values,
+            ..self
+        }
+    }
+
+    /// Sets the value returned for values that are not in the domain.
+    pub fn unknown(self, unknown: f64) -> Self {
+        Self {
+            unknown: Some(unknown),
+            ..self
+        }
+    }
+
+    /// Returns the position of the point for the given domain value, or the
+    /// unknown value if it is not in the domain.  Returns `None` if it is not
+    /// in the domain and no unknown value is set.
+    pub fn try_scale<Q>(&self, value: &Q) -> Option<f64>
+    where
+        DomainType: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.index.get(value) {
+            Some(&idx) => Some(self.values[idx]),
+            None => self.unknown,
+        }
+    }
+
+    /// Like [try_scale](ScalePoint::try_scale), but panics if the value is
+    /// not in the domain and no unknown value is set.
+    pub fn scale<Q>(&self, value: &Q) -> f64
+    where
+        DomainType: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.try_scale(value) {
+            Some(value) => value,
+            None => panic!("value is not in the domain and no unknown value is set"),
+        }
+    }
+}
+
+impl<'a, DomainType> Default for ScalePoint<'a, DomainType>
+where
+    DomainType: Clone + Eq + Hash,
+{
+    fn default() -> Self {
+        Self {
+            domain: Cow::Borrowed(&[]),
+            range: 0.0..1.0,
+            padding: 0.0,
+            align: 0.5,
+            round: false,
+            values: vec![],
+            step: std::f64::NAN,
+            unknown: None,
+            index: HashMap::new(),
+        }
+        .recalc()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScalePoint::<i32>::new();
+
+        let annotation: &[i32] = &[];
+        assert_eq!(annotation, scale.domain_values());
+        assert_eq!(0.0..1.0, scale.range);
+        assert_eq!(0.0, scale.band_width());
+        assert_eq!(1.0, scale.step());
+        assert_eq!(false, scale.round);
+        assert_eq!(0.0, scale.padding);
+        assert_eq!(0.5, scale.align);
+        assert_eq!(None, scale.unknown);
+    }
+
+    #[test]
+    fn points_span_the_range() {
+        let scale = ScalePoint::new().domain(&["a", "b", "c"]).range(0..1);
+
+        assert_eq!(0.0, scale.scale("a"));
+        assert_eq!(0.5, scale.scale("b"));
+        assert_eq!(1.0, scale.scale("c"));
+        assert_eq!(0.5, scale.step());
+    }
+
+    #[test]
+    fn padding_is_in_step_units() {
+        let scale = ScalePoint::new()
+            .domain(&["a", "b", "c"])
+            .range(0..1)
+            .padding(0.5);
+
+        assert_delta!(1.0 / 6.0, scale.scale("a"), DELTA);
+        assert_delta!(0.5, scale.scale("b"), DELTA);
+        assert_delta!(5.0 / 6.0, scale.scale("c"), DELTA);
+        assert_delta!(1.0 / 3.0, scale.step(), DELTA);
+    }
+
+    #[test]
+    fn align_distributes_the_padding() {
+        let scale = ScalePoint::new()
+            .domain(&["a", "b"])
+            .range(0..120)
+            .padding(1.0);
+
+        assert_eq!(vec![40.0, 80.0], scale.values);
+        assert_eq!(vec![0.0, 40.0], scale.clone().align(0.0).values);
+        assert_eq!(vec![80.0, 120.0], scale.clone().align(1.0).values);
+        assert_eq!(1.0, scale.align(2.0).align);
+    }
+
+    #[test]
+    fn single_point_is_aligned() {
+        let scale = ScalePoint::new().domain(&["a"]).range(0..100);
+
+        assert_eq!(50.0, scale.scale("a"));
+        assert_eq!(0.0, scale.align(0.0).scale("a"));
+    }
+
+    #[test]
+    fn round_produces_integer_positions() {
+        let scale = ScalePoint::new()
+            .domain(&["a", "b", "c", "d"])
+            .range(0..100)
+            .round(true);
+
+        assert_eq!(33.0, scale.step());
+        assert_eq!(vec![1.0, 34.0, 67.0, 100.0], scale.values);
+    }
+
+    #[test]
+    fn descending_range() {
+        let scale = ScalePoint::new().domain(&["a", "b", "c"]).range(100..0);
+
+        assert_eq!(100.0, scale.scale("a"));
+        assert_eq!(50.0, scale.scale("b"));
+        assert_eq!(0.0, scale.scale("c"));
+    }
+
+    #[test]
+    fn unknown_values() {
+        let scale = ScalePoint::new().domain(&["a", "b"]).range(0..100);
+
+        assert_eq!(Some(100.0), scale.try_scale("b"));
+        assert_eq!(None, scale.try_scale("z"));
+        assert_eq!(Some(-1.0), scale.unknown(-1.0).try_scale("z"));
+    }
+}
@@ -0,0 +1,335 @@
+use std::ops::Range;
+
+use crate::array::ticks::Ticks;
+#[cfg(feature = "color")]
+use crate::color::Hsl;
+use crate::error::Result;
+use crate::interpolate::*;
+use crate::scale::continuous::*;
+use crate::scale::linear::nice_linear;
+
+/// Power scales are similar to [linear](crate::scale::ScaleLinear) scales,
+/// except an exponential transform is applied to the input domain value
+/// before the output range value is computed. Each range value y can be
+/// expressed as a function of the domain value x: y = mx^k + b, where k is the
+/// exponent value. Power scales also support negative domain values, in which
+/// case the input value and the resulting output value are multiplied by -1.
+#[derive(Clone, Debug)]
+pub struct ScalePow<RangeType, InterpolatorType> {
+    pub domain: Range<f64>,
+    pub range: Range<RangeType>,
+    pub clamped: bool,
+    pub exponent: f64,
+    pub interpolator: InterpolatorType,
+}
+
+impl<'a, RangeType, InterpolatorType> ScalePow<RangeType, InterpolatorType>
+where
+    InterpolatorType: RangeInterpolator<'a, RangeType>,
+{
+    pub fn interpolator<NewInterpolator>(
+        self,
+        interpolator: NewInterpolator,
+    ) -> ScalePow<RangeType, NewInterpolator>
+    where
+        NewInterpolator: RangeInterpolator<'a, RangeType>,
+    {
+        ScalePow {
+            interpolator,
+            domain: self.domain,
+            range: self.range,
+            clamped: self.clamped,
+            exponent: self.exponent,
+        }
+    }
+
+    pub fn exponent(self, exponent: f64) -> Self {
+        Self { exponent, ..self }
+    }
+
+    fn transform(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            -(-x).powf(self.exponent)
+        } else {
+            x.powf(self.exponent)
+        }
+    }
+}
+
+impl<'a, RangeType, InterpolatorType> ScaleContinuous<'a, f64, RangeType>
+    for ScalePow<RangeType, InterpolatorType>
+where
+    InterpolatorType: RangeInterpolator<'a, RangeType>,
+{
+    fn domain<DomainIntermediateType>(self, domain: Range<DomainIntermediateType>) -> Result<Self>
+    where
+        DomainIntermediateType: Into<f64> + PartialOrd,
+    {
+        Ok(Self {
+            domain: domain.start.into()..domain.end.into(),
+            ..self
+        })
+    }
+
+    fn range<RangeIntermediateType>(self, range: Range<RangeIntermediateType>) -> Result<Self>
+    where
+        RangeIntermediateType: Into<RangeType>,
+    {
+        Ok(Self {
+            range: range.start.into()..range.end.into(),
+            ..self
+        })
+    }
+
+    fn clamped(self, clamped: bool) -> Self {
+        Self { clamped, ..self }
+    }
+
+    /// Like [ScaleLinear](crate::scale::ScaleLinear), the domain is extended
+    /// to nice round values before the exponent is applied.
+    fn nice<CountType>(self, count: Option<CountType>) -> Result<Self>
+    where
+        CountType: Into<i32>,
+    {
+        let count = match count {
+            Some(count) => count.into(),
+            None => 10,
+        };
+
+        let domain = nice_linear(&self.domain, count);
+        self.domain(domain)
+    }
+
+    fn scale<DomainIntermediateType>(&'a self, t: DomainIntermediateType) -> RangeType
+    where
+        DomainIntermediateType: Into<f64>,
+        RangeType: Copy,
+    {
+        let t = t.into();
+
+        let clamped = match self.clamped {
+            true => t.clamp(
+                self.domain.start.min(self.domain.end),
+                self.domain.start.max(self.domain.end),
+            ),
+            false => t,
+        };
+
+        let domain_start = self.transform(self.domain.start);
+        let domain_end = self.transform(self.domain.end);
+
+        let normalized = (self.transform(clamped) - domain_start) / (domain_end - domain_start);
+
+        self.interpolator.interpolate_range(&self.range, normalized)
+    }
+
+    /// Like [ScaleLinear](crate::scale::ScaleLinear), the ticks are uniformly
+    /// spaced within the domain, not the transformed domain.
+    fn ticks(&self, tick_count: Option<i32>) -> Vec<f64> {
+        self.domain.ticks(tick_count)
+    }
+}
+
+impl ScalePow<f64, NumberInterpolator> {
+    pub fn new() -> Self {
+        Self {
+            domain: 0.0..1.0,
+            range: 0.0..1.0,
+            clamped: false,
+            exponent: 1.0,
+            interpolator: NumberInterpolator::new(),
+        }
+    }
+
+    /// Constructs a square root scale, a power scale with an exponent of 0.5.
+    /// Square root scales are useful for sizing circles by area rather than
+    /// by radius.
+    pub fn sqrt() -> Self {
+        Self::new().exponent(0.5)
+    }
+}
+
+#[cfg(feature = "color")]
+impl ScalePow<Hsl, HslInterpolator> {
+    pub fn new() -> Self {
+        Self {
+            domain: 0.0..1.0,
+            range: Hsl {
+                hue: 0.0,
+                saturation: 1.0,
+                lightness: 0.5,
+            }..Hsl {
+                hue: 240.0,
+                saturation: 1.0,
+                lightness: 0.5,
+            },
+            clamped: false,
+            exponent: 1.0,
+            interpolator: HslInterpolator::new(),
+        }
+    }
+
+    /// Constructs a square root scale, a power scale with an exponent of 0.5.
+    /// Square root scales are useful for sizing circles by area rather than
+    /// by radius.
+    pub fn sqrt() -> Self {
+        Self::new().exponent(0.5)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScalePow::<f64, _>::new();
+
+        assert_eq!(0.0..1.0, scale.domain);
+        assert_eq!(0.0..1.0, scale.range);
+        assert_eq!(false, scale.clamped);
+        assert_eq!(1.0, scale.exponent);
+    }
+
+    #[test]
+    fn sqrt_has_an_exponent_of_one_half() {
+        let scale = ScalePow::<f64, _>::sqrt();
+
+        assert_eq!(0.0..1.0, scale.domain);
+        assert_eq!(0.0..1.0, scale.range);
+        assert_eq!(0.5, scale.exponent);
+        assert_delta!(0.5, scale.scale(0.25), DELTA);
+    }
+
+    #[test]
+    fn x_is_mapped_to_y() {
+        let scale = ScalePow::<f64, _>::new().exponent(0.5);
+
+        assert_delta!(std::f64::consts::FRAC_1_SQRT_2, scale.scale(0.5), DELTA);
+        assert_delta!(1.0, scale.scale(1.0), DELTA);
+    }
+
+    #[test]
+    fn exponent_changes_the_transform() -> Result<()> {
+        let scale = ScalePow::<f64, _>::new()
+            .exponent(2.0)
+            .domain(1..2)?
+            .range(0..3)?;
+
+        assert_delta!(0.0, scale.scale(1.0), DELTA);
+        assert_delta!(1.25, scale.scale(1.5), DELTA);
+        assert_delta!(3.0, scale.scale(2.0), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn domain_can_be_negative() -> Result<()> {
+        let scale = ScalePow::<f64, _>::new().exponent(2.0).domain(-1..1)?;
+
+        // The sign is kept, so -0.5 maps to -0.25 before normalization.
+        assert_delta!(0.0, scale.scale(-1.0), DELTA);
+        assert_delta!(0.375, scale.scale(-0.5), DELTA);
+        assert_delta!(0.5, scale.scale(0.0), DELTA);
+        assert_delta!(0.625, scale.scale(0.5), DELTA);
+        assert_delta!(1.0, scale.scale(1.0), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn clamped() -> Result<()> {
+        let scale = ScalePow::<f64, _>::new().exponent(2.0).clamped(true);
+
+        assert_delta!(0.0, scale.scale(-0.5), DELTA);
+        assert_delta!(1.0, scale.scale(1.5), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn round_interpolator() -> Result<()> {
+        let scale = ScalePow::<f64, _>::sqrt()
+            .interpolator(RoundInterpolator::new())
+            .domain(0..100)?
+            .range(0..10)?;
+
+        assert_eq!(5.0, scale.scale(25.0));
+        assert_eq!(7.0, scale.scale(50.0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn nice_extends_the_domain_to_round_numbers() -> Result<()> {
+        let scale = ScalePow::<f64, _>::new();
+
+        assert_eq!(
+            1.0..11.0,
+            scale.clone().domain(1.1..10.9)?.nice(None::<i32>)?.domain
+        );
+        assert_eq!(
+            0.0..12.0,
+            scale.clone().domain(0.7..11.001)?.nice(None::<i32>)?.domain
+        );
+        assert_eq!(
+            0.0..0.5,
+            scale.clone().domain(0.0..0.49)?.nice(None::<i32>)?.domain
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ticks_are_linear_in_the_domain() -> Result<()> {
+        let scale = ScalePow::<f64, _>::sqrt().domain(0..10)?;
+
+        assert_eq!(vec![0.0, 5.0, 10.0], scale.ticks(Some(2)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn sqrt_maps_area_to_radius() -> Result<()> {
+        let scale = ScalePow::<f64, _>::sqrt().range(0..10)?;
+
+        assert_eq!(0.5, scale.exponent);
+        assert_delta!(5.0, scale.scale(0.25), DELTA);
+        assert_delta!(7.0710678, scale.scale(0.5), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn descending_domain() -> Result<()> {
+        let scale = ScalePow::<f64, _>::sqrt().domain(100..0)?.range(0..10)?;
+
+        assert_delta!(0.0, scale.scale(100), DELTA);
+        assert_delta!(5.0, scale.scale(25), DELTA);
+        assert_delta!(10.0, scale.scale(0), DELTA);
+        assert_delta!(0.0, scale.clone().clamped(true).scale(150), DELTA);
+        assert_eq!(vec![100.0, 50.0, 0.0], scale.ticks(Some(2)));
+
+        let scale = scale.domain(10.9..1.1)?.nice(None::<i32>)?;
+        assert_eq!(11.0..1.0, scale.domain);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn color() {
+        let scale = ScalePow::<Hsl, _>::sqrt();
+
+        assert_eq!(
+            scale.scale(0.25),
+            Hsl {
+                hue: 300.0,
+                saturation: 1.0,
+                lightness: 0.5
+            }
+        );
+    }
+}
@@ -10,6 +10,7 @@ pub struct ScaleQuantile<'a, RangeType> {
     pub domain: Vec<f64>,
     pub range: &'a [RangeType],
     pub thresholds: Vec<f64>,
+    pub unknown: Option<&'a RangeType>,
 }
 
 impl<'a, RangeType> ScaleQuantile<'a, RangeType>
@@ -47,14 +48,22 @@ impl<'a, RangeType> ScaleQuantile<'a, RangeType>
         Self { thresholds, ..self }
     }
 
-    pub fn scale<DomainIntermediateType>(&self, n: DomainIntermediateType) -> &'a RangeType
+    /// Sets the value returned for NaN input values.
+    pub fn unknown(self, unknown: &'a RangeType) -> Self {
+        Self { unknown: Some(unknown), ..self }
+    }
+
+    /// Returns the range value for the quantile containing the given value,
+    /// or the unknown value if the input is NaN or the range is empty.
+    /// Returns `None` in those cases if no unknown value is set.
+    pub fn try_scale<DomainIntermediateType>(&self, n: DomainIntermediateType) -> Option<&'a RangeType>
     where
         DomainIntermediateType: Into<f64>
     {
         let n = n.into();
 
         if n.is_nan() {
-            unimplemented!("TODO: Implement 'unknown' values")
+            return self.unknown;
         }
 
         let position = self.thresholds.binary_search_by(|probe| {
@@ -65,7 +74,19 @@ impl<'a, RangeType> ScaleQuantile<'a, RangeType>
             Err(n) => n,
         };
 
-        &self.range[position]
+        self.range.get(position).or(self.unknown)
+    }
+
+    /// Like [try_scale](ScaleQuantile::try_scale), but panics if the input is
+    /// NaN or the range is empty and no unknown value is set.
+    pub fn scale<DomainIntermediateType>(&self, n: DomainIntermediateType) -> &'a RangeType
+    where
+        DomainIntermediateType: Into<f64>
+    {
+        match self.try_scale(n) {
+            Some(value) => value,
+            None => panic!("value can't be mapped and no unknown value is set"),
+        }
     }
 }
 
@@ -75,6 +96,7 @@ impl<'a, T> ScaleQuantile<'a, T> {
             domain: vec![],
             range: &[],
             thresholds: vec![],
+            unknown: None,
         }
     }
 }
@@ -89,7 +111,7 @@ mod tests {
 
         assert!(scale.domain.is_empty());
         assert!(scale.range.is_empty());
-        // test.equal(s.unknown(), undefined);
+        assert_eq!(None, scale.unknown);
     }
 
     #[test]
@@ -135,7 +157,20 @@ mod tests {
         }
     }
 
-    // tape("quantile(x) returns undefined if the input value is NaN", function(test)
+    #[test]
+    fn nan_input_returns_unknown() {
+        let scale = ScaleQuantile::<i32>::new()
+            .domain(&[3, 6, 7, 8, 8, 10, 13, 15, 16, 20])
+            .range(&[0, 1, 2, 3]);
+
+        assert_eq!(None, scale.try_scale(std::f64::NAN));
+        assert_eq!(Some(&0), scale.try_scale(3));
+
+        let scale = scale.unknown(&-1);
+
+        assert_eq!(Some(&-1), scale.try_scale(std::f64::NAN));
+        assert_eq!(-1, *scale.scale(std::f64::NAN));
+    }
 
     #[test]
     fn domain_values_are_sorted() {
@@ -0,0 +1,201 @@
+use std::ops::Range;
+
+/// Quantize scales are similar to [linear](crate::scale::ScaleLinear) scales,
+/// except they use a discrete rather than continuous range. The continuous
+/// input domain is divided into uniform segments based on the number of
+/// values in (i.e., the cardinality of) the output range. Each range value y
+/// can be expressed as a quantized linear function of the domain value x: y =
+/// m round(x) + b.
+#[derive(Clone, Debug)]
+pub struct ScaleQuantize<'a, RangeType> {
+    pub domain: Range<f64>,
+    pub range: &'a [RangeType],
+    pub unknown: Option<&'a RangeType>,
+    pub thresholds: Vec<f64>,
+}
+
+impl<'a, RangeType> ScaleQuantize<'a, RangeType> {
+    pub fn new() -> Self {
+        Self {
+            domain: 0.0..1.0,
+            range: &[],
+            unknown: None,
+            thresholds: vec![],
+        }
+    }
+
+    pub fn domain<DomainIntermediateType>(self, domain: Range<DomainIntermediateType>) -> Self
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        Self {
+            domain: domain.start.into()..domain.end.into(),
+            ..self
+        }
+        .rescale()
+    }
+
+    pub fn range(self, range: &'a [RangeType]) -> Self {
+        Self { range, ..self }.rescale()
+    }
+
+    /// Returns the thresholds between the uniform segments of the domain.
+    /// There is one fewer threshold than there are values in the range.
+    pub fn thresholds(&self) -> &[f64] {
+        &self.thresholds
+    }
+
+    fn rescale(self) -> Self {
+        let n = self.range.len().max(1) as f64;
+        let x0 = self.domain.start;
+        let x1 = self.domain.end;
+
+        let thresholds = (1..self.range.len())
+            .map(|i| (i as f64 * x1 + (n - i as f64) * x0) / n)
+            .collect();
+
+        Self { thresholds, ..self }
+    }
+
+    /// Sets the value returned for NaN input values.
+    pub fn unknown(self, unknown: &'a RangeType) -> Self {
+        Self {
+            unknown: Some(unknown),
+            ..self
+        }
+    }
+
+    /// Returns the range value for the segment containing the given value, or
+    /// the unknown value if the input is NaN or the range is empty.  Returns
+    /// `None` in those cases if no unknown value is set.
+    pub fn try_scale<DomainIntermediateType>(
+        &self,
+        n: DomainIntermediateType,
+    ) -> Option<&'a RangeType>
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        let n = n.into();
+
+        if n.is_nan() {
+            return self.unknown;
+        }
+
+        let position = self.thresholds.iter().take_while(|t| **t <= n).count();
+
+        self.range.get(position).or(self.unknown)
+    }
+
+    /// Like [try_scale](ScaleQuantize::try_scale), but panics if the input is
+    /// NaN or the range is empty and no unknown value is set.
+    pub fn scale<DomainIntermediateType>(&self, n: DomainIntermediateType) -> &'a RangeType
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        match self.try_scale(n) {
+            Some(value) => value,
+            None => panic!("value can't be mapped and no unknown value is set"),
+        }
+    }
+
+    /// Returns the extent of values in the domain for the given value in the
+    /// range, or `None` if the value isn't in the range.  This is useful for
+    /// interaction, say to determine the values in the domain that correspond
+    /// to the pixel location under the mouse.
+    pub fn invert_extent(&self, value: &RangeType) -> Option<Range<f64>>
+    where
+        RangeType: PartialEq,
+    {
+        let i = self.range.iter().position(|x| x == value)?;
+
+        let start = match i {
+            0 => self.domain.start,
+            i => self.thresholds[i - 1],
+        };
+        let end = match self.thresholds.get(i) {
+            Some(threshold) => *threshold,
+            None => self.domain.end,
+        };
+
+        Some(start..end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScaleQuantize::<f64>::new();
+
+        assert_eq!(0.0..1.0, scale.domain);
+        assert!(scale.range.is_empty());
+        assert!(scale.thresholds().is_empty());
+    }
+
+    #[test]
+    fn x_is_mapped_to_a_segment() {
+        let scale = ScaleQuantize::new().range(&[0, 1, 2]);
+
+        assert_eq!(0, *scale.scale(0.0));
+        assert_eq!(0, *scale.scale(0.2));
+        assert_eq!(1, *scale.scale(0.4));
+        assert_eq!(1, *scale.scale(0.6));
+        assert_eq!(2, *scale.scale(0.8));
+        assert_eq!(2, *scale.scale(1.0));
+    }
+
+    #[test]
+    fn values_outside_the_domain_are_clamped() {
+        let scale = ScaleQuantize::new().domain(0..1).range(&["a", "b", "c"]);
+
+        assert_eq!("a", *scale.scale(-0.5));
+        assert_eq!("c", *scale.scale(1.5));
+    }
+
+    #[test]
+    fn thresholds_divide_the_domain_uniformly() {
+        let scale = ScaleQuantize::new().range(&[0, 1, 2]);
+        assert_eq!(2, scale.thresholds().len());
+        assert_delta!(1.0 / 3.0, scale.thresholds()[0], DELTA);
+        assert_delta!(2.0 / 3.0, scale.thresholds()[1], DELTA);
+
+        let scale = scale.range(&[0, 1, 2, 3]);
+        assert_eq!(&[0.25, 0.5, 0.75], scale.thresholds());
+
+        let scale = scale.domain(10..30);
+        assert_eq!(&[15.0, 20.0, 25.0], scale.thresholds());
+    }
+
+    #[test]
+    fn invert_extent_maps_a_value_to_its_segment() {
+        let scale = ScaleQuantize::new().domain(0..3).range(&["a", "b", "c"]);
+
+        assert_eq!(Some(0.0..1.0), scale.invert_extent(&"a"));
+        assert_eq!(Some(1.0..2.0), scale.invert_extent(&"b"));
+        assert_eq!(Some(2.0..3.0), scale.invert_extent(&"c"));
+        assert_eq!(None, scale.invert_extent(&"d"));
+    }
+
+    #[test]
+    fn invert_extent_uses_the_first_of_duplicate_values() {
+        let scale = ScaleQuantize::new().domain(0..3).range(&["a", "b", "a"]);
+
+        assert_eq!(Some(0.0..1.0), scale.invert_extent(&"a"));
+    }
+
+    #[test]
+    fn nan_input_returns_unknown() {
+        let scale = ScaleQuantize::new().range(&["a", "b"]);
+
+        assert_eq!(None, scale.try_scale(std::f64::NAN));
+
+        let scale = scale.unknown(&"z");
+
+        assert_eq!(Some(&"z"), scale.try_scale(std::f64::NAN));
+        assert_eq!("z", *scale.scale(std::f64::NAN));
+    }
+}
@@ -0,0 +1,202 @@
+use std::ops::Range;
+
+use crate::array::ticks::Ticks;
+use crate::error::Result;
+use crate::scale::continuous::*;
+use crate::scale::linear::nice_linear;
+
+/// Radial scales are a variant of [linear](crate::scale::ScaleLinear) scales
+/// where the range is internally squared so that an input value corresponds
+/// linearly to the squared output value. These scales are useful when you
+/// want the input value to correspond to the area of a graphical mark and the
+/// mark is specified by radius, as in a radial bar chart. Radial scales do not
+/// support interpolators.
+#[derive(Clone, Debug)]
+pub struct ScaleRadial {
+    pub domain: Range<f64>,
+    pub range: Range<f64>,
+    pub clamped: bool,
+    pub round: bool,
+}
+
+impl ScaleRadial {
+    pub fn new() -> Self {
+        Self {
+            domain: 0.0..1.0,
+            range: 0.0..1.0,
+            clamped: false,
+            round: false,
+        }
+    }
+
+    /// Enables or disables rounding of the output to the nearest integer.
+    pub fn round(self, round: bool) -> Self {
+        Self { round, ..self }
+    }
+}
+
+fn square(x: f64) -> f64 {
+    x.signum() * x * x
+}
+
+fn unsquare(x: f64) -> f64 {
+    x.signum() * x.abs().sqrt()
+}
+
+impl<'a> ScaleContinuous<'a, f64, f64> for ScaleRadial {
+    fn domain<DomainIntermediateType>(self, domain: Range<DomainIntermediateType>) -> Result<Self>
+    where
+        DomainIntermediateType: Into<f64> + PartialOrd,
+    {
+        Ok(Self {
+            domain: domain.start.into()..domain.end.into(),
+            ..self
+        })
+    }
+
+    fn range<RangeIntermediateType>(self, range: Range<RangeIntermediateType>) -> Result<Self>
+    where
+        RangeIntermediateType: Into<f64>,
+    {
+        Ok(Self {
+            range: range.start.into()..range.end.into(),
+            ..self
+        })
+    }
+
+    fn clamped(self, clamped: bool) -> Self {
+        Self { clamped, ..self }
+    }
+
+    fn nice<CountType>(self, count: Option<CountType>) -> Result<Self>
+    where
+        CountType: Into<i32>,
+    {
+        let count = match count {
+            Some(count) => count.into(),
+            None => 10,
+        };
+
+        let domain = nice_linear(&self.domain, count);
+        self.domain(domain)
+    }
+
+    fn scale<DomainIntermediateType>(&'a self, t: DomainIntermediateType) -> f64
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        let t = t.into();
+
+        let clamped = match self.clamped {
+            true => t.clamp(
+                self.domain.start.min(self.domain.end),
+                self.domain.start.max(self.domain.end),
+            ),
+            false => t,
+        };
+
+        let normalized = (clamped - self.domain.start) / (self.domain.end - self.domain.start);
+
+        let range_start = square(self.range.start);
+        let range_end = square(self.range.end);
+        let squared = range_start * (1.0 - normalized) + range_end * normalized;
+
+        match self.round {
+            true => unsquare(squared).round(),
+            false => unsquare(squared),
+        }
+    }
+
+    fn ticks(&self, tick_count: Option<i32>) -> Vec<f64> {
+        self.domain.ticks(tick_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScaleRadial::new();
+
+        assert_eq!(0.0..1.0, scale.domain);
+        assert_eq!(0.0..1.0, scale.range);
+        assert_eq!(false, scale.clamped);
+        assert_eq!(false, scale.round);
+    }
+
+    #[test]
+    fn x_is_mapped_to_y() -> Result<()> {
+        let scale = ScaleRadial::new().range(1..2)?;
+
+        assert_delta!(1.5811388, scale.scale(0.5), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn squared_range_is_linear_in_the_domain() -> Result<()> {
+        let scale = ScaleRadial::new().domain(0..100)?.range(0..10)?;
+
+        assert_delta!(0.0, scale.scale(0), DELTA);
+        assert_delta!(5.0, scale.scale(25), DELTA);
+        assert_delta!(7.0710678, scale.scale(50), DELTA);
+        assert_delta!(10.0, scale.scale(100), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn negative_range_keeps_its_sign() -> Result<()> {
+        let scale = ScaleRadial::new().range(-1..-2)?;
+
+        assert_delta!(-1.5811388, scale.scale(0.5), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn clamped_and_rounded() -> Result<()> {
+        let scale = ScaleRadial::new()
+            .domain(0..100)?
+            .range(0..10)?
+            .clamped(true)
+            .round(true);
+
+        assert_eq!(0.0, scale.scale(-50));
+        assert_eq!(7.0, scale.scale(50));
+        assert_eq!(10.0, scale.scale(150));
+
+        Ok(())
+    }
+
+    #[test]
+    fn nice_and_ticks_use_the_domain() -> Result<()> {
+        let scale = ScaleRadial::new()
+            .domain(1.1..10.9)?
+            .range(0..100)?
+            .nice(None::<i32>)?;
+
+        assert_eq!(1.0..11.0, scale.domain);
+        assert_eq!(vec![2.0, 4.0, 6.0, 8.0, 10.0], scale.ticks(Some(5)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn descending_domain() -> Result<()> {
+        let scale = ScaleRadial::new().domain(100..0)?.range(0..10)?;
+
+        assert_delta!(0.0, scale.scale(100), DELTA);
+        assert_delta!(7.0710678, scale.scale(50), DELTA);
+        assert_delta!(10.0, scale.scale(0), DELTA);
+
+        let scale = scale.clamped(true);
+        assert_delta!(10.0, scale.scale(-50), DELTA);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,342 @@
+use std::ops::Range;
+
+use crate::array::ticks::Ticks;
+use crate::error::Result;
+use crate::scale::continuous::*;
+use crate::scale::linear::nice_linear;
+use crate::scale::{ScaleLog, ScaleSymlog};
+
+/// The transform applied to domain values by the
+/// [sequential](crate::scale::ScaleSequential) and
+/// [diverging](crate::scale::ScaleDiverging) scales before they are
+/// normalized, matching the transforms of the linear, log, power and symlog
+/// scales.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScaleTransform {
+    Linear,
+    /// A logarithmic transform with the given base.  Negative domains are
+    /// reflected, as with [ScaleLog](crate::scale::ScaleLog).
+    Log(f64),
+    /// A power transform with the given exponent.
+    Pow(f64),
+    /// A symmetric log transform with the given constant.
+    Symlog(f64),
+}
+
+impl ScaleTransform {
+    pub(crate) fn apply(&self, x: f64) -> f64 {
+        match *self {
+            ScaleTransform::Linear => x,
+            ScaleTransform::Log(_) if x < 0.0 => -(-x).ln(),
+            ScaleTransform::Log(_) => x.ln(),
+            ScaleTransform::Pow(exponent) => x.signum() * x.abs().powf(exponent),
+            ScaleTransform::Symlog(constant) => x.signum() * (x.abs() / constant).ln_1p(),
+        }
+    }
+
+    /// Returns the ticks of the scale with this transform over the given
+    /// domain, in the order of the domain.
+    pub(crate) fn ticks(&self, domain: &Range<f64>, count: i32) -> Vec<f64> {
+        let descending = domain.end < domain.start;
+        let ascending = match descending {
+            true => domain.end..domain.start,
+            false => domain.clone(),
+        };
+
+        let mut ticks = match *self {
+            ScaleTransform::Linear | ScaleTransform::Pow(_) => ascending.ticks(Some(count)),
+            ScaleTransform::Log(base) => match ScaleLog::new().base(base).domain(ascending) {
+                Ok(scale) => scale.ticks(Some(count)),
+                Err(_) => vec![],
+            },
+            ScaleTransform::Symlog(constant) => {
+                match ScaleSymlog::<f64, _>::new()
+                    .constant(constant)
+                    .domain(ascending)
+                {
+                    Ok(scale) => scale.ticks(Some(count)),
+                    Err(_) => vec![],
+                }
+            }
+        };
+
+        if descending {
+            ticks.reverse();
+        }
+
+        ticks
+    }
+
+    /// Extends the given domain to nice round values as the scale with this
+    /// transform would.
+    pub(crate) fn nice(&self, domain: &Range<f64>, count: i32) -> Result<Range<f64>> {
+        let descending = domain.end < domain.start;
+        let ascending = match descending {
+            true => domain.end..domain.start,
+            false => domain.clone(),
+        };
+
+        let nice = match *self {
+            ScaleTransform::Log(base) => {
+                let domain = ScaleLog::new()
+                    .base(base)
+                    .domain(ascending)?
+                    .nice(Some(count))?
+                    .domain;
+
+                domain[0]..domain[domain.len() - 1]
+            }
+            _ => nice_linear(&ascending, count),
+        };
+
+        Ok(match descending {
+            true => nice.end..nice.start,
+            false => nice,
+        })
+    }
+}
+
+/// Sequential scales map a continuous domain to the output of an interpolator
+/// function, typically a color ramp such as those of d3-scale-chromatic.  The
+/// interpolator is called with the domain value normalized to [0, 1].
+///
+/// Log, power and symlog flavors are available through the respective
+/// constructors or the [transform](ScaleSequential::transform) method.
+pub struct ScaleSequential<'a, RangeType> {
+    pub domain: Range<f64>,
+    pub clamped: bool,
+    pub transform: ScaleTransform,
+    interpolator: Box<dyn Fn(f64) -> RangeType + 'a>,
+}
+
+impl<'a, RangeType> ScaleSequential<'a, RangeType> {
+    /// Constructs a sequential scale with the given interpolator, a domain of
+    /// [0, 1] and a linear transform.
+    pub fn new(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self {
+            domain: 0.0..1.0,
+            clamped: false,
+            transform: ScaleTransform::Linear,
+            interpolator,
+        }
+    }
+
+    /// Constructs a sequential scale with a base 10 logarithmic transform and
+    /// a domain of [1, 10].
+    pub fn log(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self {
+            domain: 1.0..10.0,
+            transform: ScaleTransform::Log(10.0),
+            ..Self::new(interpolator)
+        }
+    }
+
+    /// Constructs a sequential scale with a power transform with an exponent
+    /// of 1.
+    pub fn pow(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self::new(interpolator).transform(ScaleTransform::Pow(1.0))
+    }
+
+    /// Constructs a sequential scale with a power transform with an exponent
+    /// of 0.5.
+    pub fn sqrt(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self::new(interpolator).transform(ScaleTransform::Pow(0.5))
+    }
+
+    /// Constructs a sequential scale with a symmetric log transform with a
+    /// constant of 1.
+    pub fn symlog(interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self::new(interpolator).transform(ScaleTransform::Symlog(1.0))
+    }
+
+    pub fn interpolator(self, interpolator: Box<dyn Fn(f64) -> RangeType + 'a>) -> Self {
+        Self {
+            interpolator,
+            ..self
+        }
+    }
+
+    pub fn transform(self, transform: ScaleTransform) -> Self {
+        Self { transform, ..self }
+    }
+
+    pub fn domain<DomainIntermediateType>(self, domain: Range<DomainIntermediateType>) -> Self
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        Self {
+            domain: domain.start.into()..domain.end.into(),
+            ..self
+        }
+    }
+
+    pub fn clamped(self, clamped: bool) -> Self {
+        Self { clamped, ..self }
+    }
+
+    pub fn nice(self, count: Option<i32>) -> Result<Self> {
+        let domain = self.transform.nice(&self.domain, count.unwrap_or(10))?;
+
+        Ok(Self { domain, ..self })
+    }
+
+    pub fn scale<DomainIntermediateType>(&self, t: DomainIntermediateType) -> RangeType
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        let t0 = self.transform.apply(self.domain.start);
+        let t1 = self.transform.apply(self.domain.end);
+
+        // As in d3, a degenerate domain maps every value to the middle of the
+        // interpolator.
+        if t0 == t1 {
+            return (self.interpolator)(0.5);
+        }
+
+        let normalized = (self.transform.apply(t.into()) - t0) / (t1 - t0);
+
+        let normalized = match self.clamped {
+            true => normalized.clamp(0.0, 1.0),
+            false => normalized,
+        };
+
+        (self.interpolator)(normalized)
+    }
+
+    pub fn ticks(&self, count: Option<i32>) -> Vec<f64> {
+        self.transform.ticks(&self.domain, count.unwrap_or(10))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScaleSequential::new(Box::new(|t| t));
+
+        assert_eq!(0.0..1.0, scale.domain);
+        assert_eq!(false, scale.clamped);
+        assert_eq!(ScaleTransform::Linear, scale.transform);
+        assert_eq!(0.5, scale.scale(0.5));
+    }
+
+    #[test]
+    fn domain_is_normalized_for_the_interpolator() {
+        let scale = ScaleSequential::new(Box::new(|t| t * 2.0)).domain(-1..3);
+
+        assert_eq!(0.0, scale.scale(-1));
+        assert_eq!(0.5, scale.scale(0));
+        assert_eq!(2.0, scale.scale(3));
+        assert_eq!(3.0, scale.scale(5));
+
+        let scale = scale.clamped(true);
+
+        assert_eq!(0.0, scale.scale(-3));
+        assert_eq!(2.0, scale.scale(5));
+    }
+
+    #[test]
+    fn descending_domain() {
+        let scale = ScaleSequential::new(Box::new(|t| t)).domain(10..0);
+
+        assert_eq!(0.0, scale.scale(10));
+        assert_eq!(0.25, scale.scale(7.5));
+        assert_eq!(1.0, scale.scale(0));
+        assert_eq!(vec![10.0, 5.0, 0.0], scale.ticks(Some(2)));
+    }
+
+    #[test]
+    fn degenerate_domain_maps_to_one_half() {
+        let scale = ScaleSequential::new(Box::new(|t| t)).domain(2..2);
+
+        assert_eq!(0.5, scale.scale(2));
+        assert_eq!(0.5, scale.scale(3));
+        assert_eq!(0.5, scale.clamped(true).scale(-1));
+    }
+
+    #[test]
+    fn log_flavor() {
+        let scale = ScaleSequential::log(Box::new(|t| t));
+
+        assert_eq!(1.0..10.0, scale.domain);
+        assert_delta!(0.69897, scale.scale(5), DELTA);
+        assert_eq!(
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0],
+            scale.ticks(None)
+        );
+
+        let scale = scale.domain(-1..-100);
+        assert_delta!(0.5, scale.scale(-10), DELTA);
+    }
+
+    #[test]
+    fn pow_and_sqrt_flavors() {
+        let scale = ScaleSequential::pow(Box::new(|t| t)).transform(ScaleTransform::Pow(2.0));
+        assert_delta!(0.25, scale.scale(0.5), DELTA);
+
+        let scale = ScaleSequential::sqrt(Box::new(|t| t)).domain(0..100);
+        assert_delta!(0.5, scale.scale(25), DELTA);
+        assert_eq!(vec![0.0, 50.0, 100.0], scale.ticks(Some(2)));
+    }
+
+    #[test]
+    fn symlog_flavor() {
+        let scale = ScaleSequential::symlog(Box::new(|t| t)).domain(-100..100);
+
+        assert_delta!(0.0, scale.scale(-100), DELTA);
+        assert_delta!(0.5, scale.scale(0), DELTA);
+        assert_delta!(0.9259721, scale.scale(50), DELTA);
+    }
+
+    #[test]
+    fn nice_extends_the_domain() -> Result<()> {
+        let scale = ScaleSequential::new(Box::new(|t| t))
+            .domain(1.1..10.9)
+            .nice(None)?;
+
+        assert_eq!(1.0..11.0, scale.domain);
+
+        let scale = scale.domain(10.9..1.1).nice(None)?;
+
+        assert_eq!(11.0..1.0, scale.domain);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn color_interpolator() {
+        use crate::color::Hsl;
+        use crate::interpolate::{HslInterpolator, RangeInterpolator};
+
+        let interpolator = HslInterpolator::new();
+        let scale = ScaleSequential::new(Box::new(move |t| {
+            interpolator.interpolate_range(
+                &(Hsl {
+                    hue: 0.0,
+                    saturation: 1.0,
+                    lightness: 0.5,
+                }..Hsl {
+                    hue: 120.0,
+                    saturation: 1.0,
+                    lightness: 0.5,
+                }),
+                t,
+            )
+        }))
+        .domain(0..200);
+
+        assert_eq!(
+            Hsl {
+                hue: 60.0,
+                saturation: 1.0,
+                lightness: 0.5
+            },
+            scale.scale(100)
+        );
+    }
+}
@@ -0,0 +1,364 @@
+use std::ops::Range;
+
+use crate::array::ticks::Ticks;
+#[cfg(feature = "color")]
+use crate::color::Hsl;
+use crate::error::Result;
+use crate::interpolate::*;
+use crate::scale::continuous::*;
+use crate::scale::linear::nice_linear;
+
+/// Symmetric log scales are similar to [log](crate::scale::ScaleLog) scales,
+/// except the transform is linear close to zero, so the domain may include or
+/// cross zero. The mapping to the range value y can be expressed as a
+/// function of the domain value x: y = m sign(x) log(1 + |x / c|) + b, where c
+/// is the constant. See [A bi-symmetric log transformation for wide-range
+/// data](https://www.researchgate.net/profile/John_Webber4/publication/233967063_A_bi-symmetric_log_transformation_for_wide-range_data/links/0fcfd50d791c85082e000000.pdf)
+/// by Webber for details.
+#[derive(Clone, Debug)]
+pub struct ScaleSymlog<RangeType, InterpolatorType> {
+    pub domain: Range<f64>,
+    pub range: Range<RangeType>,
+    pub clamped: bool,
+    pub constant: f64,
+    pub interpolator: InterpolatorType,
+}
+
+impl<'a, RangeType, InterpolatorType> ScaleSymlog<RangeType, InterpolatorType>
+where
+    InterpolatorType: RangeInterpolator<'a, RangeType>,
+{
+    pub fn interpolator<NewInterpolator>(
+        self,
+        interpolator: NewInterpolator,
+    ) -> ScaleSymlog<RangeType, NewInterpolator>
+    where
+        NewInterpolator: RangeInterpolator<'a, RangeType>,
+    {
+        ScaleSymlog {
+            interpolator,
+            domain: self.domain,
+            range: self.range,
+            clamped: self.clamped,
+            constant: self.constant,
+        }
+    }
+
+    /// Sets the constant of the transform, which defaults to 1.  The transform
+    /// is approximately linear for values whose magnitude is below the
+    /// constant.
+    pub fn constant(self, constant: f64) -> Self {
+        Self { constant, ..self }
+    }
+
+    fn transform(&self, x: f64) -> f64 {
+        x.signum() * (x.abs() / self.constant).ln_1p()
+    }
+}
+
+impl<'a, RangeType, InterpolatorType> ScaleContinuous<'a, f64, RangeType>
+    for ScaleSymlog<RangeType, InterpolatorType>
+where
+    InterpolatorType: RangeInterpolator<'a, RangeType>,
+{
+    fn domain<DomainIntermediateType>(self, domain: Range<DomainIntermediateType>) -> Result<Self>
+    where
+        DomainIntermediateType: Into<f64> + PartialOrd,
+    {
+        Ok(Self {
+            domain: domain.start.into()..domain.end.into(),
+            ..self
+        })
+    }
+
+    fn range<RangeIntermediateType>(self, range: Range<RangeIntermediateType>) -> Result<Self>
+    where
+        RangeIntermediateType: Into<RangeType>,
+    {
+        Ok(Self {
+            range: range.start.into()..range.end.into(),
+            ..self
+        })
+    }
+
+    fn clamped(self, clamped: bool) -> Self {
+        Self { clamped, ..self }
+    }
+
+    fn nice<CountType>(self, count: Option<CountType>) -> Result<Self>
+    where
+        CountType: Into<i32>,
+    {
+        let count = match count {
+            Some(count) => count.into(),
+            None => 10,
+        };
+
+        let domain = nice_linear(&self.domain, count);
+        self.domain(domain)
+    }
+
+    fn scale<DomainIntermediateType>(&'a self, t: DomainIntermediateType) -> RangeType
+    where
+        DomainIntermediateType: Into<f64>,
+        RangeType: Copy,
+    {
+        let t = t.into();
+
+        let clamped = match self.clamped {
+            true => t.clamp(
+                self.domain.start.min(self.domain.end),
+                self.domain.start.max(self.domain.end),
+            ),
+            false => t,
+        };
+
+        let domain_start = self.transform(self.domain.start);
+        let domain_end = self.transform(self.domain.end);
+
+        let normalized = (self.transform(clamped) - domain_start) / (domain_end - domain_start);
+
+        self.interpolator.interpolate_range(&self.range, normalized)
+    }
+
+    /// If the domain reaches more than an order of magnitude past the
+    /// constant, returns zero (when it is within the domain) and the powers of
+    /// ten within the domain from the constant outwards on either side of
+    /// zero, keeping every other power (or fewer) when there would be more
+    /// than count ticks.
+    /// Otherwise the transform is close to linear and the ticks are those of
+    /// a [linear](crate::scale::ScaleLinear) scale.
+    fn ticks(&self, tick_count: Option<i32>) -> Vec<f64> {
+        let count = tick_count.unwrap_or(10);
+
+        let extent = self.domain.start.abs().max(self.domain.end.abs());
+        if extent / self.constant <= 10.0 || count <= 0 {
+            return self.domain.ticks(Some(count));
+        }
+
+        let descending = self.domain.end < self.domain.start;
+        let domain = match descending {
+            true => self.domain.end..self.domain.start,
+            false => self.domain.clone(),
+        };
+
+        // The powers of ten whose magnitudes lie between near and far, from
+        // the constant outwards.
+        let first_power = self.constant.log10().ceil() as i32;
+        let powers = |near: f64, far: f64| -> Vec<f64> {
+            let lowest_power = (near.log10().floor() as i32).max(first_power);
+            let highest_power = far.log10().floor() as i32;
+
+            (lowest_power..=highest_power)
+                .map(|power| 10_f64.powi(power))
+                .filter(|magnitude| near <= *magnitude && *magnitude <= far)
+                .collect()
+        };
+
+        let negative = match domain.start < 0.0 {
+            true => powers((-domain.end).max(0.0), -domain.start),
+            false => vec![],
+        };
+        let positive = match domain.end > 0.0 {
+            true => powers(domain.start.max(0.0), domain.end),
+            false => vec![],
+        };
+
+        let stride = ((negative.len() + positive.len()) as f64 / count as f64).ceil() as usize;
+        let stride = stride.max(1);
+
+        let mut ticks: Vec<f64> = negative
+            .iter()
+            .step_by(stride)
+            .rev()
+            .map(|magnitude| -magnitude)
+            .collect();
+
+        if domain.start <= 0.0 && domain.end >= 0.0 {
+            ticks.push(0.0);
+        }
+
+        ticks.extend(positive.iter().step_by(stride));
+
+        if descending {
+            ticks.reverse();
+        }
+
+        ticks
+    }
+}
+
+impl ScaleSymlog<f64, NumberInterpolator> {
+    pub fn new() -> Self {
+        Self {
+            domain: 0.0..1.0,
+            range: 0.0..1.0,
+            clamped: false,
+            constant: 1.0,
+            interpolator: NumberInterpolator::new(),
+        }
+    }
+}
+
+#[cfg(feature = "color")]
+impl ScaleSymlog<Hsl, HslInterpolator> {
+    pub fn new() -> Self {
+        Self {
+            domain: 0.0..1.0,
+            range: Hsl {
+                hue: 0.0,
+                saturation: 1.0,
+                lightness: 0.5,
+            }..Hsl {
+                hue: 240.0,
+                saturation: 1.0,
+                lightness: 0.5,
+            },
+            clamped: false,
+            constant: 1.0,
+            interpolator: HslInterpolator::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScaleSymlog::<f64, _>::new();
+
+        assert_eq!(0.0..1.0, scale.domain);
+        assert_eq!(0.0..1.0, scale.range);
+        assert_eq!(false, scale.clamped);
+        assert_eq!(1.0, scale.constant);
+    }
+
+    #[test]
+    fn x_is_mapped_to_y() -> Result<()> {
+        let scale = ScaleSymlog::<f64, _>::new().domain(-100..100)?;
+
+        assert_delta!(0.0, scale.scale(-100), DELTA);
+        assert_delta!(0.5, scale.scale(0), DELTA);
+        assert_delta!(0.9259721, scale.scale(50), DELTA);
+        assert_delta!(1.0, scale.scale(100), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn domain_can_be_negative() -> Result<()> {
+        let scale = ScaleSymlog::<f64, _>::new().domain(-10..0)?;
+
+        assert_delta!(0.0, scale.scale(-10), DELTA);
+        assert_delta!(0.2527782, scale.scale(-5), DELTA);
+        assert_delta!(1.0, scale.scale(0), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn constant_changes_the_transform() -> Result<()> {
+        let scale = ScaleSymlog::<f64, _>::new().constant(5.0);
+        assert_eq!(5.0, scale.constant);
+
+        let scale = scale.domain(-1..1)?;
+        assert_delta!(0.7613793, scale.scale(0.5), DELTA);
+
+        let scale = scale.domain(0..100)?;
+        assert_delta!(0.3608488, scale.scale(10), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn clamped() -> Result<()> {
+        let scale = ScaleSymlog::<f64, _>::new().domain(-10..10)?.clamped(true);
+
+        assert_delta!(0.0, scale.scale(-100), DELTA);
+        assert_delta!(1.0, scale.scale(100), DELTA);
+
+        Ok(())
+    }
+
+    #[test]
+    fn ticks_are_linear_close_to_zero() -> Result<()> {
+        let scale = ScaleSymlog::<f64, _>::new().domain(-5..5)?;
+
+        assert_eq!(
+            vec![-5.0, -4.0, -3.0, -2.0, -1.0, 0.0, 1.0, 2.0, 3.0, 4.0, 5.0],
+            scale.ticks(None)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ticks_are_powers_of_ten_over_a_wide_domain() -> Result<()> {
+        let scale = ScaleSymlog::<f64, _>::new().domain(-1000..100000)?;
+
+        assert_eq!(
+            vec![-1000.0, -100.0, -10.0, -1.0, 0.0, 1.0, 10.0, 100.0, 1000.0, 10000.0, 100000.0],
+            scale.ticks(None)
+        );
+
+        assert_eq!(
+            vec![-100.0, -1.0, 0.0, 1.0, 100.0, 10000.0],
+            scale.ticks(Some(5))
+        );
+
+        let scale = scale.constant(10.0);
+        assert_eq!(
+            vec![-1000.0, -100.0, -10.0, 0.0, 10.0, 100.0, 1000.0, 10000.0, 100000.0],
+            scale.ticks(None)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ticks_stay_within_a_domain_excluding_zero() -> Result<()> {
+        let scale = ScaleSymlog::<f64, _>::new().domain(500..100000)?;
+        assert_eq!(vec![1000.0, 10000.0, 100000.0], scale.ticks(None));
+
+        let scale = scale.domain(-100000..-500)?;
+        assert_eq!(vec![-100000.0, -10000.0, -1000.0], scale.ticks(None));
+
+        let scale = scale.domain(1e3..1e9)?;
+        assert_eq!(vec![1e3, 1e5, 1e7, 1e9], scale.ticks(Some(4)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn descending_domain() -> Result<()> {
+        let scale = ScaleSymlog::<f64, _>::new().domain(100000..-1000)?;
+
+        assert_delta!(0.0, scale.scale(100000), DELTA);
+        assert_delta!(1.0, scale.scale(-1000), DELTA);
+        assert_delta!(1.0, scale.clone().clamped(true).scale(-5000), DELTA);
+        assert_eq!(
+            vec![100000.0, 10000.0, 1000.0, 100.0, 10.0, 1.0, 0.0, -1.0, -10.0, -100.0, -1000.0],
+            scale.ticks(None)
+        );
+
+        let scale = scale.domain(98.2..-9.5)?.nice(None::<i32>)?;
+        assert_eq!(100.0..-10.0, scale.domain);
+
+        Ok(())
+    }
+
+    #[test]
+    fn nice_extends_the_domain_to_round_numbers() -> Result<()> {
+        let scale = ScaleSymlog::<f64, _>::new()
+            .domain(-9.5..98.2)?
+            .nice(None::<i32>)?;
+
+        assert_eq!(-10.0..100.0, scale.domain);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use std::ops::Range;
+
+/// Threshold scales are similar to [quantize](crate::scale::ScaleQuantize)
+/// scales, except they allow you to map arbitrary subsets of the domain to
+/// discrete values in the range. The input domain is still continuous, and
+/// divided into slices based on a set of threshold values.  If the number of
+/// values in the scale's range is n + 1, the number of values in the domain
+/// must be n.
+#[derive(Clone, Debug)]
+pub struct ScaleThreshold<'a, RangeType> {
+    pub domain: Vec<f64>,
+    pub range: &'a [RangeType],
+    pub unknown: Option<&'a RangeType>,
+}
+
+impl<'a, RangeType> ScaleThreshold<'a, RangeType> {
+    pub fn new() -> Self {
+        Self {
+            domain: vec![],
+            range: &[],
+            unknown: None,
+        }
+    }
+
+    /// Sets the thresholds, which must be in ascending order.
+    pub fn domain<DomainIntermediateType>(self, domain: &[DomainIntermediateType]) -> Self
+    where
+        DomainIntermediateType: Into<f64> + Clone,
+    {
+        let domain = domain.iter().cloned().map(|datum| datum.into()).collect();
+
+        Self { domain, ..self }
+    }
+
+    pub fn range(self, range: &'a [RangeType]) -> Self {
+        Self { range, ..self }
+    }
+
+    /// Returns the thresholds, which are the domain of the scale.
+    pub fn thresholds(&self) -> &[f64] {
+        &self.domain
+    }
+
+    /// Sets the value returned for NaN input values.
+    pub fn unknown(self, unknown: &'a RangeType) -> Self {
+        Self {
+            unknown: Some(unknown),
+            ..self
+        }
+    }
+
+    /// Returns the range value for the slice containing the given value, or
+    /// the unknown value if the input is NaN or the range is empty.  Returns
+    /// `None` in those cases if no unknown value is set.
+    pub fn try_scale<DomainIntermediateType>(
+        &self,
+        n: DomainIntermediateType,
+    ) -> Option<&'a RangeType>
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        let n = n.into();
+
+        if n.is_nan() {
+            return self.unknown;
+        }
+
+        let len = self.domain.len().min(self.range.len().saturating_sub(1));
+        let position = self.domain[..len].iter().take_while(|t| **t <= n).count();
+
+        self.range.get(position).or(self.unknown)
+    }
+
+    /// Like [try_scale](ScaleThreshold::try_scale), but panics if the input is
+    /// NaN or the range is empty and no unknown value is set.
+    pub fn scale<DomainIntermediateType>(&self, n: DomainIntermediateType) -> &'a RangeType
+    where
+        DomainIntermediateType: Into<f64>,
+    {
+        match self.try_scale(n) {
+            Some(value) => value,
+            None => panic!("value can't be mapped and no unknown value is set"),
+        }
+    }
+
+    /// Returns the extent of values in the domain for the given value in the
+    /// range, or `None` if the value isn't in the range.  The first and last
+    /// extents are unbounded, so they start at negative infinity and end at
+    /// infinity respectively.
+    pub fn invert_extent(&self, value: &RangeType) -> Option<Range<f64>>
+    where
+        RangeType: PartialEq,
+    {
+        let i = self.range.iter().position(|x| x == value)?;
+
+        let start = match i {
+            0 => std::f64::NEG_INFINITY,
+            i => *self.domain.get(i - 1)?,
+        };
+        let end = match self.domain.get(i) {
+            Some(threshold) => *threshold,
+            None => std::f64::INFINITY,
+        };
+
+        Some(start..end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_defaults() {
+        let scale = ScaleThreshold::<f64>::new();
+
+        assert!(scale.domain.is_empty());
+        assert!(scale.range.is_empty());
+    }
+
+    #[test]
+    fn x_is_mapped_to_a_slice() {
+        let scale = ScaleThreshold::new()
+            .domain(&[1.0 / 3.0, 2.0 / 3.0])
+            .range(&["a", "b", "c"]);
+
+        assert_eq!("a", *scale.scale(0.0));
+        assert_eq!("a", *scale.scale(0.2));
+        assert_eq!("b", *scale.scale(0.4));
+        assert_eq!("b", *scale.scale(0.6));
+        assert_eq!("c", *scale.scale(0.8));
+        assert_eq!("c", *scale.scale(1.0));
+    }
+
+    #[test]
+    fn thresholds_are_inclusive_of_the_upper_slice() {
+        let scale = ScaleThreshold::new()
+            .domain(&[0, 1])
+            .range(&["a", "b", "c"]);
+
+        assert_eq!("a", *scale.scale(-1));
+        assert_eq!("b", *scale.scale(0));
+        assert_eq!("b", *scale.scale(0.5));
+        assert_eq!("c", *scale.scale(1));
+        assert_eq!("c", *scale.scale(1000));
+        assert_eq!(&[0.0, 1.0], scale.thresholds());
+    }
+
+    #[test]
+    fn extra_thresholds_are_ignored() {
+        let scale = ScaleThreshold::new().domain(&[0, 1, 2]).range(&["a", "b"]);
+
+        assert_eq!("a", *scale.scale(-1));
+        assert_eq!("b", *scale.scale(1.5));
+        assert_eq!("b", *scale.scale(3));
+    }
+
+    #[test]
+    fn invert_extent_maps_a_value_to_its_slice() {
+        let scale = ScaleThreshold::new()
+            .domain(&[1, 2])
+            .range(&["a", "b", "c"]);
+
+        assert_eq!(Some(std::f64::NEG_INFINITY..1.0), scale.invert_extent(&"a"));
+        assert_eq!(Some(1.0..2.0), scale.invert_extent(&"b"));
+        assert_eq!(Some(2.0..std::f64::INFINITY), scale.invert_extent(&"c"));
+        assert_eq!(None, scale.invert_extent(&"d"));
+    }
+
+    #[test]
+    fn nan_input_returns_unknown() {
+        let scale = ScaleThreshold::new().range(&["a", "b"]);
+
+        assert_eq!(None, scale.try_scale(std::f64::NAN));
+
+        let scale = scale.unknown(&"z");
+
+        assert_eq!(Some(&"z"), scale.try_scale(std::f64::NAN));
+        assert_eq!("z", *scale.scale(std::f64::NAN));
+    }
+}
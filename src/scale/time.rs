@@ -14,10 +14,13 @@ const RFC_3339_FMT: &str = "%Y-%m-%dT%H:%M:%S";
 /// domain values are coerced to dates rather than numbers, and invert likewise
 /// returns a date. Time scales implement ticks based on calendar intervals,
 /// taking the pain out of generating axes for temporal domains.
+///
+/// As with [linear](crate::scale::ScaleLinear) scales, the domain and range
+/// may have more than two stops.
 #[derive(Clone, Debug)]
 pub struct ScaleTime<RangeType, InterpolatorType> {
-    pub domain: Range<NaiveDateTime>,
-    pub range: Range<RangeType>,
+    pub domain: Vec<NaiveDateTime>,
+    pub range: Vec<RangeType>,
     pub clamped: bool,
     pub interpolator: InterpolatorType,
 }
@@ -30,8 +33,8 @@ impl<'a> ScaleTime<f64, NumberInterpolator> {
             .expect("Date parsing failed?");
 
         Self {
-            domain: epoch_start..epoch_end,
-            range: 0.0..1.0,
+            domain: vec![epoch_start, epoch_end],
+            range: vec![0.0, 1.0],
             clamped: false,
             interpolator: NumberInterpolator::new(),
         }
@@ -53,6 +56,86 @@ impl<'a> ScaleTime<f64, NumberInterpolator> {
     }
 }
 
+impl<'a, RangeType, InterpolatorType> ScaleTime<RangeType, InterpolatorType>
+where
+    InterpolatorType: RangeInterpolator<'a, RangeType>,
+{
+    /// Sets the domain to the given stops, of which there must be at least
+    /// two, in ascending or descending order.
+    pub fn domain_stops<DomainIntermediateType>(
+        self,
+        domain: &[DomainIntermediateType],
+    ) -> Result<Self>
+    where
+        DomainIntermediateType: Into<NaiveDateTime> + Clone,
+    {
+        let domain: Vec<NaiveDateTime> = domain.iter().cloned().map(|stop| stop.into()).collect();
+
+        check_stops(&domain)?;
+        let ascending = domain.windows(2).all(|pair| pair[0] <= pair[1]);
+        let descending = domain.windows(2).all(|pair| pair[0] >= pair[1]);
+        if !(ascending || descending) {
+            return Err(BuckyError::NonMonotonicDomain);
+        }
+
+        Ok(Self { domain, ..self })
+    }
+
+    /// Sets the range to the given stops, of which there must be at least
+    /// two.
+    pub fn range_stops<RangeIntermediateType>(
+        self,
+        range: &[RangeIntermediateType],
+    ) -> Result<Self>
+    where
+        RangeIntermediateType: Into<RangeType> + Clone,
+    {
+        let range: Vec<RangeType> = range.iter().cloned().map(|stop| stop.into()).collect();
+
+        check_stops(&range)?;
+
+        Ok(Self { range, ..self })
+    }
+
+    /// Given a value from the range, returns the corresponding date from the
+    /// domain, to the nearest millisecond.  This is useful for reading a date
+    /// back from a position, e.g. under the mouse or the edges of a brush.
+    /// If the scale is clamped the date is clamped to the domain.  Returns an
+    /// error if the range isn't numeric or is empty, or if the date can't be
+    /// represented.
+    pub fn invert<RangeIntermediateType>(
+        &'a self,
+        value: RangeIntermediateType,
+    ) -> Result<NaiveDateTime>
+    where
+        RangeIntermediateType: Into<RangeType>,
+        RangeType: Clone,
+    {
+        let n = self.domain.len().min(self.range.len());
+        let (i, t) = polyinvert(&self.range[..n], &self.interpolator, value.into())?;
+        let t = match self.clamped {
+            true => t.clamp(0.0, 1.0),
+            false => t,
+        };
+
+        let span = (self.domain[i + 1] - self.domain[i]).num_milliseconds() as f64;
+        let offset = (t * span).round();
+
+        if !t.is_finite() || !offset.is_finite() || offset.abs() >= std::i64::MAX as f64 {
+            return Err(BuckyError::DateOutOfRange);
+        }
+
+        self.domain[i]
+            .checked_add_signed(chrono::Duration::milliseconds(offset as i64))
+            .ok_or(BuckyError::DateOutOfRange)
+    }
+
+    /// Returns the first and last stops of the domain.
+    pub fn extent(&self) -> Range<NaiveDateTime> {
+        self.domain[0]..self.domain[self.domain.len() - 1]
+    }
+}
+
 impl<'a, RangeType, InterpolatorType> ScaleContinuous<'a, NaiveDateTime, RangeType>
     for ScaleTime<RangeType, InterpolatorType>
 where
@@ -63,14 +146,7 @@ where
     where
         DomainIntermediateType: Into<NaiveDateTime> + PartialOrd,
     {
-        if domain.start > domain.end {
-            return Err(BuckyError::DescendingScale);
-        }
-
-        Ok(Self {
-            domain: domain.start.into()..domain.end.into(),
-            ..self
-        })
+        self.domain_stops(&[domain.start.into(), domain.end.into()])
     }
 
     fn range<RangeIntermediateType>(self, range: Range<RangeIntermediateType>) -> Result<Self>
@@ -78,7 +154,7 @@ where
         RangeIntermediateType: Into<RangeType>,
     {
         Ok(Self {
-            range: range.start.into()..range.end.into(),
+            range: vec![range.start.into(), range.end.into()],
             ..self
         })
     }
@@ -96,14 +172,22 @@ where
             None => 10,
         };
 
-        match self.domain.tick_increment(count) {
+        let extent = self.extent();
+        let last = self.domain.len() - 1;
+        let (extent, i0, i1) = match extent.end < extent.start {
+            true => (extent.end..extent.start, last, 0),
+            false => (extent, 0, last),
+        };
+
+        match extent.tick_increment(count) {
             TickDuration::Milliseconds(0) |
             TickDuration::Seconds(0) => Ok(self),
             increment @ _ => {
-                let round_start = increment.floor(&self.domain.start);
-                let round_end = increment.ceil(&self.domain.end);
+                let mut domain = self.domain;
+                domain[i0] = increment.floor(&extent.start);
+                domain[i1] = increment.ceil(&extent.end);
 
-                self.domain(round_start..round_end)
+                Ok(Self { domain, ..self })
             }
         }
     }
@@ -113,19 +197,20 @@ where
         DomainIntermediateType: Into<NaiveDateTime>,
         RangeType: Copy,
     {
+        let start = self.domain[0];
+
         let clamped = match self.clamped {
-            true => self.domain.start.max(self.domain.end.min(t.into())),
+            true => {
+                let (min, max) = clamp_bounds(&self.domain, self.range.len());
+                t.into().clamp(min, max)
+            }
             false => t.into(),
         };
 
         // TODO: Proper bounds checking
-        let start = (clamped - self.domain.start).num_nanoseconds().unwrap() as f64;
-        let end = (self.domain.end - self.domain.start)
-            .num_nanoseconds()
-            .unwrap() as f64;
-        let normalized: f64 = start / end;
+        let nanoseconds = |d: &NaiveDateTime| (*d - start).num_nanoseconds().unwrap() as f64;
 
-        self.interpolator.interpolate_range(&self.range, normalized)
+        polymap(&self.domain, &self.range, &self.interpolator, nanoseconds, nanoseconds(&clamped))
     }
 
     fn ticks(&self, tick_count: Option<i32>) -> Vec<NaiveDateTime> {
@@ -134,17 +219,27 @@ where
             None => 10,
         };
 
-        let interval : TickDuration = self.domain.tick_increment(tick_count);
+        let extent = self.extent();
+        let descending = extent.end < extent.start;
+        let extent = match descending {
+            true => extent.end..extent.start,
+            false => extent,
+        };
+        let interval : TickDuration = extent.tick_increment(tick_count);
         let calendar_duration : CalendarDuration = CalendarDuration::from(&interval);
 
         let mut ticks = vec![];
-        let mut cur = self.domain.start;
+        let mut cur = extent.start;
 
-        while cur <= self.domain.end {
+        while cur <= extent.end {
             ticks.push(cur);
             cur = calendar_duration::naive_checked_add(&cur, &calendar_duration).expect("Date math failed");
         }
 
+        if descending {
+            ticks.reverse();
+        }
+
         ticks
     }
 }
@@ -184,8 +279,7 @@ mod tests {
 
         let d0 = NaiveDateTime::parse_from_str("2000-01-01T00:00:00", RFC_3339_FMT)?;
         let d1 = NaiveDateTime::parse_from_str("2000-01-02T00:00:00", RFC_3339_FMT)?;
-        assert_eq!(scale.domain.start, d0);
-        assert_eq!(scale.domain.end, d1);
+        assert_eq!(vec![d0, d1], scale.domain);
 
         Ok(())
     }
@@ -200,8 +294,7 @@ mod tests {
 
         let d0 = NaiveDateTime::parse_from_str("2000-01-01T00:00:00", RFC_3339_FMT).unwrap();
         let d1 = NaiveDateTime::parse_from_str("2140-01-01T00:00:00", RFC_3339_FMT).unwrap();
-        assert_eq!(scale.domain.start, d0);
-        assert_eq!(scale.domain.end, d1);
+        assert_eq!(vec![d0, d1], scale.domain);
 
         Ok(())
     }
@@ -213,7 +306,7 @@ mod tests {
 
         let scale = ScaleTime::new().domain(d0..d1)?.nice(None::<i32>)?;
 
-        assert_eq!(d0..d1, scale.domain);
+        assert_eq!(vec![d0, d1], scale.domain);
 
         Ok(())
     }
@@ -232,7 +325,7 @@ mod tests {
             let d1 = NaiveDateTime::parse_from_str("2009-01-01T23:45:00", RFC_3339_FMT).unwrap();
             let domain = scale.clone().nice(Some(100))?.domain;
 
-            assert_eq!(d0..d1, domain);
+            assert_eq!(vec![d0, d1], domain);
         }
 
         {
@@ -240,7 +333,7 @@ mod tests {
             let d1 = NaiveDateTime::parse_from_str("2009-01-02T00:00:00", RFC_3339_FMT).unwrap();
             let domain = scale.clone().nice(Some(10))?.domain;
 
-            assert_eq!(d0..d1, domain);
+            assert_eq!(vec![d0, d1], domain);
         }
 
         Ok(())
@@ -279,4 +372,84 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn invert_maps_numbers_to_dates() -> Result<()> {
+        let d0 = NaiveDateTime::parse_from_str("2009-01-01T00:00:00", RFC_3339_FMT)?;
+        let d1 = NaiveDateTime::parse_from_str("2010-01-01T00:00:00", RFC_3339_FMT)?;
+        let scale = ScaleTime::new().domain(d0..d1)?;
+
+        assert_eq!(d0, scale.invert(0.0)?);
+        assert_eq!(
+            NaiveDateTime::parse_from_str("2009-07-02T12:00:00", RFC_3339_FMT)?,
+            scale.invert(0.5)?
+        );
+        assert_eq!(d1, scale.invert(1.0)?);
+        assert_eq!(d1, scale.clamped(true).invert(2.0)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn descending_domain() -> Result<()> {
+        let d0 = NaiveDateTime::parse_from_str("2000-01-01T00:17:00", RFC_3339_FMT)?;
+        let d1 = NaiveDateTime::parse_from_str("2000-01-01T23:42:00", RFC_3339_FMT)?;
+        let ascending = ScaleTime::new().domain(d0..d1)?;
+        let scale = ScaleTime::new().domain(d1..d0)?;
+
+        assert_eq!(0.0, scale.scale(d1));
+        assert_eq!(1.0, scale.scale(d0));
+        assert_eq!(0.0, scale.clone().clamped(true).scale(d1 + chrono::Duration::hours(1)));
+        assert_eq!(d0, scale.invert(1.0)?);
+
+        let mut ticks = ascending.ticks(None);
+        ticks.reverse();
+        assert_eq!(ticks, scale.ticks(None));
+
+        let start = NaiveDateTime::parse_from_str("2000-01-01T00:00:00", RFC_3339_FMT)?;
+        let end = NaiveDateTime::parse_from_str("2000-01-02T00:00:00", RFC_3339_FMT)?;
+        assert_eq!(vec![end, start], scale.nice(None::<i32>)?.domain);
+
+        Ok(())
+    }
+
+    #[test]
+    fn invert_fails_when_the_date_overflows() -> Result<()> {
+        let d0 = NaiveDateTime::parse_from_str("2009-01-01T00:00:00", RFC_3339_FMT)?;
+        let d1 = NaiveDateTime::parse_from_str("2010-01-01T00:00:00", RFC_3339_FMT)?;
+        let scale = ScaleTime::new().domain(d0..d1)?;
+
+        assert!(matches!(scale.invert(1e6), Err(BuckyError::DateOutOfRange)));
+        assert!(matches!(scale.invert(1e300), Err(BuckyError::DateOutOfRange)));
+        assert!(matches!(
+            scale.invert(std::f64::INFINITY),
+            Err(BuckyError::DateOutOfRange)
+        ));
+        assert_eq!(d1, scale.clamped(true).invert(1e300)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn polylinear_domain_maps_each_segment() -> Result<()> {
+        let d0 = NaiveDateTime::parse_from_str("2009-01-01T00:00:00", RFC_3339_FMT)?;
+        let d1 = NaiveDateTime::parse_from_str("2009-01-02T00:00:00", RFC_3339_FMT)?;
+        let d2 = NaiveDateTime::parse_from_str("2009-01-04T00:00:00", RFC_3339_FMT)?;
+        let scale = ScaleTime::new()
+            .domain_stops(&[d0, d1, d2])?
+            .range_stops(&[0, 100, 120])?;
+
+        let point = NaiveDateTime::parse_from_str("2009-01-03T00:00:00", RFC_3339_FMT)?;
+
+        assert_eq!(50.0, scale.scale(d0 + chrono::Duration::hours(12)));
+        assert_eq!(110.0, scale.scale(point));
+        assert_eq!(point, scale.invert(110)?);
+        assert_eq!(vec![d0, d1, d2], scale.domain);
+
+        let scale = scale.range_stops(&[0, 100])?.clamped(true);
+
+        assert_eq!(100.0, scale.scale(point));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,564 @@
+//! The arc generator produces a circular or annular sector, as in a pie or
+//! donut chart.
+//!
+//! Arcs are always centered at ⟨0,0⟩; use a transform to move the arc to a
+//! different position.  Angles are specified in radians; 0 is at -y (12
+//! o’clock) and positive angles proceed clockwise.
+
+use std::f64::consts::PI;
+
+use crate::path::{PathContext, SvgPath};
+use crate::shape::pie::PieArc;
+
+const EPSILON: f64 = 1e-12;
+const HALF_PI: f64 = PI / 2.0;
+const TAU: f64 = 2.0 * PI;
+
+/// `Arc` is an arc generator.
+pub struct Arc<'a, SourceType> {
+    inner_radius: Box<dyn FnMut(&SourceType) -> f64 + 'a>,
+    outer_radius: Box<dyn FnMut(&SourceType) -> f64 + 'a>,
+    corner_radius: Box<dyn FnMut(&SourceType) -> f64 + 'a>,
+    pad_radius: Option<Box<dyn FnMut(&SourceType) -> f64 + 'a>>,
+    start_angle: Box<dyn FnMut(&SourceType) -> f64 + 'a>,
+    end_angle: Box<dyn FnMut(&SourceType) -> f64 + 'a>,
+    pad_angle: Box<dyn FnMut(&SourceType) -> f64 + 'a>,
+    digits: Option<usize>,
+}
+
+struct CornerTangents {
+    cx: f64,
+    cy: f64,
+    x01: f64,
+    y01: f64,
+    x11: f64,
+    y11: f64,
+}
+
+fn acos(x: f64) -> f64 {
+    if x > 1.0 {
+        0.0
+    } else if x < -1.0 {
+        PI
+    } else {
+        x.acos()
+    }
+}
+
+fn asin(x: f64) -> f64 {
+    if x >= 1.0 {
+        HALF_PI
+    } else if x <= -1.0 {
+        -HALF_PI
+    } else {
+        x.asin()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn intersect(
+    x0: f64,
+    y0: f64,
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+    x3: f64,
+    y3: f64,
+) -> Option<(f64, f64)> {
+    let x10 = x1 - x0;
+    let y10 = y1 - y0;
+    let x32 = x3 - x2;
+    let y32 = y3 - y2;
+    let t = y32 * x10 - x32 * y10;
+
+    if t * t < EPSILON {
+        return None;
+    }
+
+    let t = (x32 * (y0 - y2) - y32 * (x0 - x2)) / t;
+
+    Some((x0 + t * x10, y0 + t * y10))
+}
+
+// Compute perpendicular offset line of length rc.
+// http://mathworld.wolfram.com/Circle-LineIntersection.html
+fn corner_tangents(
+    x0: f64,
+    y0: f64,
+    x1: f64,
+    y1: f64,
+    r1: f64,
+    rc: f64,
+    cw: bool,
+) -> CornerTangents {
+    let x01 = x0 - x1;
+    let y01 = y0 - y1;
+    let lo = if cw { rc } else { -rc } / (x01 * x01 + y01 * y01).sqrt();
+    let ox = lo * y01;
+    let oy = -lo * x01;
+    let x11 = x0 + ox;
+    let y11 = y0 + oy;
+    let x10 = x1 + ox;
+    let y10 = y1 + oy;
+    let x00 = (x11 + x10) / 2.0;
+    let y00 = (y11 + y10) / 2.0;
+    let dx = x10 - x11;
+    let dy = y10 - y11;
+    let d2 = dx * dx + dy * dy;
+    let r = r1 - rc;
+    let big_d = x11 * y10 - x10 * y11;
+    let d = if dy < 0.0 { -1.0 } else { 1.0 } * (0_f64).max(r * r * d2 - big_d * big_d).sqrt();
+    let mut cx0 = (big_d * dy - dx * d) / d2;
+    let mut cy0 = (-big_d * dx - dy * d) / d2;
+    let cx1 = (big_d * dy + dx * d) / d2;
+    let cy1 = (-big_d * dx + dy * d) / d2;
+    let dx0 = cx0 - x00;
+    let dy0 = cy0 - y00;
+    let dx1 = cx1 - x00;
+    let dy1 = cy1 - y00;
+
+    // Pick the closer of the two intersection points.
+    if dx0 * dx0 + dy0 * dy0 > dx1 * dx1 + dy1 * dy1 {
+        cx0 = cx1;
+        cy0 = cy1;
+    }
+
+    CornerTangents {
+        cx: cx0,
+        cy: cy0,
+        x01: -ox,
+        y01: -oy,
+        x11: cx0 * (r1 / r - 1.0),
+        y11: cy0 * (r1 / r - 1.0),
+    }
+}
+
+impl<'a, SourceType> Arc<'a, SourceType> {
+    /// Constructs a new arc generator with an inner radius, corner radius and
+    /// pad angle of zero.
+    pub fn new() -> Self {
+        Self {
+            inner_radius: Box::new(|_| 0.0),
+            outer_radius: Box::new(|_| unimplemented!("Outer radius accessor not implemented")),
+            corner_radius: Box::new(|_| 0.0),
+            pad_radius: None,
+            start_angle: Box::new(|_| unimplemented!("Start angle accessor not implemented")),
+            end_angle: Box::new(|_| unimplemented!("End angle accessor not implemented")),
+            pad_angle: Box::new(|_| 0.0),
+            digits: Some(3),
+        }
+    }
+
+    /// Sets the inner radius accessor.  Specifying a non-zero inner radius
+    /// produces a donut rather than a pie.
+    pub fn inner_radius(self, inner_radius: Box<dyn FnMut(&SourceType) -> f64 + 'a>) -> Self {
+        Self {
+            inner_radius,
+            ..self
+        }
+    }
+
+    /// Sets the outer radius accessor.  If the outer radius is smaller than the
+    /// inner radius, the inner and outer radii are swapped.
+    pub fn outer_radius(self, outer_radius: Box<dyn FnMut(&SourceType) -> f64 + 'a>) -> Self {
+        Self {
+            outer_radius,
+            ..self
+        }
+    }
+
+    /// Sets the corner radius accessor.  If the corner radius is greater than
+    /// zero, the corners of the arc are rounded using circles of the given
+    /// radius.  The corner radius may not be larger than (outer radius - inner
+    /// radius) / 2.
+    pub fn corner_radius(self, corner_radius: Box<dyn FnMut(&SourceType) -> f64 + 'a>) -> Self {
+        Self {
+            corner_radius,
+            ..self
+        }
+    }
+
+    /// Sets the pad radius accessor.  The pad radius determines the fixed
+    /// linear distance separating adjacent arcs, defined as pad radius * pad
+    /// angle.  If `None` the pad radius is computed as sqrt(inner radius² +
+    /// outer radius²).
+    pub fn pad_radius(self, pad_radius: Option<Box<dyn FnMut(&SourceType) -> f64 + 'a>>) -> Self {
+        Self { pad_radius, ..self }
+    }
+
+    /// Sets the start angle accessor.
+    pub fn start_angle(self, start_angle: Box<dyn FnMut(&SourceType) -> f64 + 'a>) -> Self {
+        Self {
+            start_angle,
+            ..self
+        }
+    }
+
+    /// Sets the end angle accessor.  If |end angle - start angle| ≥ τ, a
+    /// complete circle or annulus is generated rather than a sector.
+    pub fn end_angle(self, end_angle: Box<dyn FnMut(&SourceType) -> f64 + 'a>) -> Self {
+        Self { end_angle, ..self }
+    }
+
+    /// Sets the pad angle accessor.  The pad angle is converted to a fixed
+    /// linear distance separating adjacent arcs, defined as pad radius * pad
+    /// angle.
+    pub fn pad_angle(self, pad_angle: Box<dyn FnMut(&SourceType) -> f64 + 'a>) -> Self {
+        Self { pad_angle, ..self }
+    }
+
+    /// Computes the midpoint of the centerline of the arc that would be
+    /// generated for the given datum.  This is a convenient place to position
+    /// a label.
+    pub fn centroid(&mut self, datum: &SourceType) -> (f64, f64) {
+        let r = ((self.inner_radius)(datum) + (self.outer_radius)(datum)) / 2.0;
+        let a = ((self.start_angle)(datum) + (self.end_angle)(datum)) / 2.0 - HALF_PI;
+
+        (a.cos() * r, a.sin() * r)
+    }
+
+    /// Sets the number of fractional digits written by
+    /// [generate](Arc::generate).  If `None` coordinates are written at full
+    /// precision.  Defaults to three digits.
+    pub fn digits(self, digits: Option<usize>) -> Self {
+        Self { digits, ..self }
+    }
+
+    /// Generates an arc for the given datum and returns it as SVG path data.
+    pub fn generate(&mut self, datum: &SourceType) -> String {
+        let mut path = SvgPath::new().digits(self.digits);
+        self.generate_to(datum, &mut path);
+
+        path.to_string()
+    }
+
+    /// Generates an arc for the given datum, drawing it into the given path
+    /// context.
+    pub fn generate_to(&mut self, datum: &SourceType, path: &mut dyn PathContext) {
+        let mut r0 = (self.inner_radius)(datum);
+        let mut r1 = (self.outer_radius)(datum);
+        let a0 = (self.start_angle)(datum) - HALF_PI;
+        let a1 = (self.end_angle)(datum) - HALF_PI;
+        let da = (a1 - a0).abs();
+        let cw = a1 > a0;
+
+        // Ensure that the outer radius is always larger than the inner radius.
+        if r1 < r0 {
+            std::mem::swap(&mut r0, &mut r1);
+        }
+
+        if !(r1 > EPSILON) {
+            // Is it a point?
+            path.move_to(0.0, 0.0);
+        } else if da > TAU - EPSILON {
+            // Or is it a circle or annulus?
+            path.move_to(r1 * a0.cos(), r1 * a0.sin());
+            path.arc(0.0, 0.0, r1, a0, a1, !cw);
+            if r0 > EPSILON {
+                path.move_to(r0 * a1.cos(), r0 * a1.sin());
+                path.arc(0.0, 0.0, r0, a1, a0, cw);
+            }
+        } else {
+            // Or is it a circular or annular sector?
+            let mut a01 = a0;
+            let mut a11 = a1;
+            let mut a00 = a0;
+            let mut a10 = a1;
+            let mut da0 = da;
+            let mut da1 = da;
+            let ap = (self.pad_angle)(datum) / 2.0;
+            let rp = if ap > EPSILON {
+                match &mut self.pad_radius {
+                    Some(pad_radius) => pad_radius(datum),
+                    None => (r0 * r0 + r1 * r1).sqrt(),
+                }
+            } else {
+                0.0
+            };
+            let rc = ((r1 - r0).abs() / 2.0).min((self.corner_radius)(datum));
+            let mut rc0 = rc;
+            let mut rc1 = rc;
+
+            // Apply padding? Note that since r1 ≥ r0, da1 ≥ da0.
+            if rp > EPSILON {
+                let mut p0 = asin(rp / r0 * ap.sin());
+                let mut p1 = asin(rp / r1 * ap.sin());
+
+                da0 -= p0 * 2.0;
+                if da0 > EPSILON {
+                    p0 *= if cw { 1.0 } else { -1.0 };
+                    a00 += p0;
+                    a10 -= p0;
+                } else {
+                    da0 = 0.0;
+                    a00 = (a0 + a1) / 2.0;
+                    a10 = a00;
+                }
+
+                da1 -= p1 * 2.0;
+                if da1 > EPSILON {
+                    p1 *= if cw { 1.0 } else { -1.0 };
+                    a01 += p1;
+                    a11 -= p1;
+                } else {
+                    da1 = 0.0;
+                    a01 = (a0 + a1) / 2.0;
+                    a11 = a01;
+                }
+            }
+
+            let x01 = r1 * a01.cos();
+            let y01 = r1 * a01.sin();
+            let x10 = r0 * a10.cos();
+            let y10 = r0 * a10.sin();
+
+            let x11 = r1 * a11.cos();
+            let y11 = r1 * a11.sin();
+            let x00 = r0 * a00.cos();
+            let y00 = r0 * a00.sin();
+
+            // Apply rounded corners?  Restrict the corner radius according to
+            // the sector angle. If the intersection fails, it’s probably
+            // because the arc is too small, so disable the corner radius
+            // entirely.
+            if rc > EPSILON && da < PI {
+                match intersect(x01, y01, x00, y00, x11, y11, x10, y10) {
+                    Some(oc) => {
+                        let ax = x01 - oc.0;
+                        let ay = y01 - oc.1;
+                        let bx = x11 - oc.0;
+                        let by = y11 - oc.1;
+                        let kc = 1.0
+                            / (acos(
+                                (ax * bx + ay * by)
+                                    / ((ax * ax + ay * ay).sqrt() * (bx * bx + by * by).sqrt()),
+                            ) / 2.0)
+                                .sin();
+                        let lc = (oc.0 * oc.0 + oc.1 * oc.1).sqrt();
+                        rc0 = rc.min((r0 - lc) / (kc - 1.0));
+                        rc1 = rc.min((r1 - lc) / (kc + 1.0));
+                    }
+                    None => {
+                        rc0 = 0.0;
+                        rc1 = 0.0;
+                    }
+                }
+            }
+
+            if !(da1 > EPSILON) {
+                // Is the sector collapsed to a line?
+                path.move_to(x01, y01);
+            } else if rc1 > EPSILON {
+                // Does the sector’s outer ring have rounded corners?
+                let t0 = corner_tangents(x00, y00, x01, y01, r1, rc1, cw);
+                let t1 = corner_tangents(x11, y11, x10, y10, r1, rc1, cw);
+
+                path.move_to(t0.cx + t0.x01, t0.cy + t0.y01);
+
+                if rc1 < rc {
+                    // Have the corners merged?
+                    path.arc(
+                        t0.cx,
+                        t0.cy,
+                        rc1,
+                        t0.y01.atan2(t0.x01),
+                        t1.y01.atan2(t1.x01),
+                        !cw,
+                    );
+                } else {
+                    // Otherwise, draw the two corners and the ring.
+                    path.arc(
+                        t0.cx,
+                        t0.cy,
+                        rc1,
+                        t0.y01.atan2(t0.x01),
+                        t0.y11.atan2(t0.x11),
+                        !cw,
+                    );
+                    path.arc(
+                        0.0,
+                        0.0,
+                        r1,
+                        (t0.cy + t0.y11).atan2(t0.cx + t0.x11),
+                        (t1.cy + t1.y11).atan2(t1.cx + t1.x11),
+                        !cw,
+                    );
+                    path.arc(
+                        t1.cx,
+                        t1.cy,
+                        rc1,
+                        t1.y11.atan2(t1.x11),
+                        t1.y01.atan2(t1.x01),
+                        !cw,
+                    );
+                }
+            } else {
+                // Or is the outer ring just a circular arc?
+                path.move_to(x01, y01);
+                path.arc(0.0, 0.0, r1, a01, a11, !cw);
+            }
+
+            if !(r0 > EPSILON) || !(da0 > EPSILON) {
+                // Is there no inner ring, and it’s a circular sector?  Or
+                // perhaps it’s an annular sector collapsed due to padding?
+                path.line_to(x10, y10);
+            } else if rc0 > EPSILON {
+                // Does the sector’s inner ring (or point) have rounded corners?
+                let t0 = corner_tangents(x10, y10, x11, y11, r0, -rc0, cw);
+                let t1 = corner_tangents(x01, y01, x00, y00, r0, -rc0, cw);
+
+                path.line_to(t0.cx + t0.x01, t0.cy + t0.y01);
+
+                if rc0 < rc {
+                    // Have the corners merged?
+                    path.arc(
+                        t0.cx,
+                        t0.cy,
+                        rc0,
+                        t0.y01.atan2(t0.x01),
+                        t1.y01.atan2(t1.x01),
+                        !cw,
+                    );
+                } else {
+                    // Otherwise, draw the two corners and the ring.
+                    path.arc(
+                        t0.cx,
+                        t0.cy,
+                        rc0,
+                        t0.y01.atan2(t0.x01),
+                        t0.y11.atan2(t0.x11),
+                        !cw,
+                    );
+                    path.arc(
+                        0.0,
+                        0.0,
+                        r0,
+                        (t0.cy + t0.y11).atan2(t0.cx + t0.x11),
+                        (t1.cy + t1.y11).atan2(t1.cx + t1.x11),
+                        cw,
+                    );
+                    path.arc(
+                        t1.cx,
+                        t1.cy,
+                        rc0,
+                        t1.y11.atan2(t1.x11),
+                        t1.y01.atan2(t1.x01),
+                        !cw,
+                    );
+                }
+            } else {
+                // Or is the inner ring just a circular arc?
+                path.arc(0.0, 0.0, r0, a10, a00, cw);
+            }
+        }
+
+        path.close_path();
+    }
+}
+
+impl<'a, 'b, SourceType> Arc<'a, PieArc<'b, SourceType>> {
+    /// Constructs a new arc generator that reads the start, end and pad angles
+    /// from the slices generated by a [Pie](crate::shape::pie::Pie).
+    pub fn pie() -> Self {
+        Self::new()
+            .start_angle(Box::new(|datum| datum.start_angle))
+            .end_angle(Box::new(|datum| datum.end_angle))
+            .pad_angle(Box::new(|datum| datum.pad_angle))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    #[test]
+    fn zero_radius_is_a_point() {
+        // test.pathEqual(shape.arc().innerRadius(0).outerRadius(0).startAngle(0).endAngle(2 * Math.PI)(), "M0,0Z");
+        let mut arc = Arc::<()>::new()
+            .outer_radius(Box::new(|_| 0.0))
+            .start_angle(Box::new(|_| 0.0))
+            .end_angle(Box::new(|_| TAU));
+
+        assert_eq!("M0,0Z", arc.generate(&()));
+    }
+
+    #[test]
+    fn circles_and_sectors() {
+        let mut arc = Arc::<(f64, f64, f64)>::new()
+            .inner_radius(Box::new(|datum| datum.0))
+            .outer_radius(Box::new(|_| 100.0))
+            .start_angle(Box::new(|datum| datum.1))
+            .end_angle(Box::new(|datum| datum.2))
+            .digits(Some(6));
+
+        // test.pathEqual(shape.arc().innerRadius(0).outerRadius(100).startAngle(0).endAngle(2 * Math.PI)(), "M0,-100A100,100,0,1,1,0,100A100,100,0,1,1,0,-100Z");
+        assert_eq!(
+            "M0,-100A100,100,0,1,1,0,100A100,100,0,1,1,0,-100Z",
+            arc.generate(&(0.0, 0.0, TAU))
+        );
+
+        // test.pathEqual(shape.arc().innerRadius(50).outerRadius(100).startAngle(0).endAngle(2 * Math.PI)(), "M0,-100A100,100,0,1,1,0,100A100,100,0,1,1,0,-100M0,-50A50,50,0,1,0,0,50A50,50,0,1,0,0,-50Z");
+        assert_eq!(
+            "M0,-100A100,100,0,1,1,0,100A100,100,0,1,1,0,-100M0,-50A50,50,0,1,0,0,50A50,50,0,1,0,0,-50Z",
+            arc.generate(&(50.0, 0.0, TAU))
+        );
+
+        // test.pathEqual(shape.arc().innerRadius(0).outerRadius(100).startAngle(0).endAngle(Math.PI / 2)(), "M0,-100A100,100,0,0,1,100,0L0,0Z");
+        assert_eq!(
+            "M0,-100A100,100,0,0,1,100,0L0,0Z",
+            arc.generate(&(0.0, 0.0, PI / 2.0))
+        );
+
+        // test.pathEqual(shape.arc().innerRadius(50).outerRadius(100).startAngle(0).endAngle(Math.PI / 2)(), "M0,-100A100,100,0,0,1,100,0L50,0A50,50,0,0,0,0,-50Z");
+        assert_eq!(
+            "M0,-100A100,100,0,0,1,100,0L50,0A50,50,0,0,0,0,-50Z",
+            arc.generate(&(50.0, 0.0, PI / 2.0))
+        );
+    }
+
+    #[test]
+    fn centroid_is_the_midpoint_of_the_centerline() {
+        let mut arc = Arc::<(f64, f64)>::new()
+            .inner_radius(Box::new(|_| 0.0))
+            .outer_radius(Box::new(|_| 100.0))
+            .start_angle(Box::new(|datum| datum.0))
+            .end_angle(Box::new(|datum| datum.1));
+
+        {
+            // test.inDelta(a.centroid({startAngle: 0, endAngle: Math.PI}), [50, 0]);
+            let (x, y) = arc.centroid(&(0.0, PI));
+            assert_delta!(50.0, x, DELTA);
+            assert_delta!(0.0, y, DELTA);
+        }
+
+        {
+            // test.inDelta(a.centroid({startAngle: 0, endAngle: Math.PI / 2}), [35.355339, -35.355339]);
+            let (x, y) = arc.centroid(&(0.0, PI / 2.0));
+            assert_delta!(35.355339, x, DELTA);
+            assert_delta!(-35.355339, y, DELTA);
+        }
+    }
+
+    #[test]
+    fn pie_reads_angles_from_slices() {
+        use crate::shape::pie::Pie;
+
+        let data = &[1.0, 1.0];
+        let slices = Pie::<f64>::new().value(Box::new(|d, _i| *d)).generate(data);
+
+        let mut arc = Arc::pie()
+            .inner_radius(Box::new(|_| 50.0))
+            .outer_radius(Box::new(|_| 100.0));
+
+        let (x, y) = arc.centroid(&slices[0]);
+        assert_delta!(75.0, x, DELTA);
+        assert_delta!(0.0, y, DELTA);
+
+        let (x, y) = arc.centroid(&slices[1]);
+        assert_delta!(-75.0, x, DELTA);
+        assert_delta!(0.0, y, DELTA);
+    }
+}
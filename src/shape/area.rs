@@ -0,0 +1,262 @@
+//! Area generators create path strings for filled regions.
+
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::rc::Rc;
+
+use crate::path::{PathContext, SvgPath};
+use crate::shape::curve::{CurveGenerator, CurveLinear};
+
+/// `Area` is a Cartesian area generator.
+///
+/// An area is defined by two bounding lines, the topline and the baseline.
+/// Typically the two lines share the same x values (x0 = x1), differing only
+/// in y value (y0 and y1); most commonly, y0 is defined as a constant
+/// representing zero. The topline is defined by x1 and y1 and is rendered
+/// first; the baseline is defined by x0 and y0 and is rendered second, with
+/// the points in reverse order.
+pub struct Area<'a, SourceType> {
+    pub curve: Box<dyn CurveGenerator>,
+    x0: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>,
+    x1: Option<Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>>,
+    y0: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>,
+    y1: Option<Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>>,
+    defined: Box<dyn FnMut(&SourceType, usize) -> bool + 'a>,
+    digits: Option<usize>,
+    phantom: PhantomData<SourceType>,
+}
+
+impl<'a, SourceType> Area<'a, SourceType> {
+    /// Constructs a new area generator.
+    pub fn new() -> Self {
+        Self {
+            curve: Box::new(CurveLinear {}),
+            x0: Box::new(|_, _| unimplemented!("X accessor not implemented")),
+            x1: None,
+            y0: Box::new(|_, _| 0.0),
+            y1: Some(Box::new(|_, _| {
+                unimplemented!("Y accessor not implemented")
+            })),
+            defined: Box::new(|_, _| true),
+            digits: Some(3),
+            phantom: PhantomData {},
+        }
+    }
+
+    /// Sets the curve factory used by the the area generator.
+    pub fn curve(self, curve: Box<dyn CurveGenerator>) -> Self {
+        Self { curve, ..self }
+    }
+
+    /// Sets the `x0` accessor to the specified closure and `x1` to `None`,
+    /// such that the topline and baseline share the same x values.
+    pub fn x(self, x: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>) -> Self {
+        Self {
+            x0: x,
+            x1: None,
+            ..self
+        }
+    }
+
+    /// Sets the `x0` accessor to the specified closure and returns the area
+    /// generator.
+    ///
+    /// The `x0` accessor is invoked for each defined element in the input data
+    /// collection.  The accessor takes two parameters: the element (of type
+    /// SourceType) and the index.  The default accessor calls the
+    /// `unimplemented!` macro resulting in a panic.
+    pub fn x0(self, x0: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>) -> Self {
+        Self { x0, ..self }
+    }
+
+    /// Sets the `x1` accessor to the specified closure and returns the area
+    /// generator.  If `x1` is `None` the previously computed `x0` value is
+    /// reused.  The default `x1` accessor is `None`.
+    pub fn x1(self, x1: Option<Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>>) -> Self {
+        Self { x1, ..self }
+    }
+
+    /// Sets the `y0` accessor to the specified closure and `y1` to `None`,
+    /// such that the topline and baseline share the same y values.
+    pub fn y(self, y: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>) -> Self {
+        Self {
+            y0: y,
+            y1: None,
+            ..self
+        }
+    }
+
+    /// Sets the `y0` accessor to the specified closure and returns the area
+    /// generator.  The default `y0` accessor always returns zero.
+    pub fn y0(self, y0: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>) -> Self {
+        Self { y0, ..self }
+    }
+
+    /// Sets the `y1` accessor to the specified closure and returns the area
+    /// generator.  If `y1` is `None` the previously computed `y0` value is
+    /// reused.  The default accessor calls the `unimplemented!` macro
+    /// resulting in a panic.
+    pub fn y1(self, y1: Option<Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>>) -> Self {
+        Self { y1, ..self }
+    }
+
+    /// Sets the `defined` accessor to the specified closure and returns the
+    /// area generator.
+    ///
+    /// The default accessor assumes that the input data is always defined.
+    /// If the given element is not defined the current area segment is ended,
+    /// and a new area segment will be generated for the next defined point.
+    /// As a result, the generated area may have several discrete segments.
+    pub fn defined(self, defined: Box<dyn FnMut(&SourceType, usize) -> bool + 'a>) -> Self {
+        Self { defined, ..self }
+    }
+
+    /// Sets the number of fractional digits written by
+    /// [generate](Area::generate).  If `None` coordinates are written at full
+    /// precision.  Defaults to three digits.
+    pub fn digits(self, digits: Option<usize>) -> Self {
+        Self { digits, ..self }
+    }
+
+    /// Generates an area for the given data and returns it as SVG path data.
+    pub fn generate(&mut self, data: &[SourceType]) -> String {
+        let mut path = SvgPath::new().digits(self.digits);
+        self.generate_to(data, &mut path);
+
+        path.to_string()
+    }
+
+    /// Generates an area for the given data, drawing it into the given path
+    /// context.
+    pub fn generate_to(&mut self, data: &[SourceType], path: &mut dyn PathContext) {
+        let mut context = self.curve.context(path);
+
+        let n = data.len();
+        let mut x0z = vec![std::f64::NAN; n];
+        let mut y0z = vec![std::f64::NAN; n];
+
+        let mut defined0 = false;
+        let mut j = 0;
+
+        for i in 0..=n {
+            let defined = i < n && (self.defined)(&data[i], i);
+
+            if defined != defined0 {
+                defined0 = defined;
+
+                if defined0 {
+                    j = i;
+                    context.start_area();
+                    context.start_line();
+                } else {
+                    context.end_line();
+                    context.start_line();
+                    for k in (j..i).rev() {
+                        context.point(x0z[k], y0z[k]);
+                    }
+                    context.end_line();
+                    context.end_area();
+                }
+            }
+
+            if defined0 {
+                let datum = &data[i];
+
+                x0z[i] = (self.x0)(datum, i);
+                y0z[i] = (self.y0)(datum, i);
+
+                let x = match &mut self.x1 {
+                    Some(x1) => x1(datum, i),
+                    None => x0z[i],
+                };
+                let y = match &mut self.y1 {
+                    Some(y1) => y1(datum, i),
+                    None => y0z[i],
+                };
+
+                context.point(x, y);
+            }
+        }
+    }
+}
+
+impl<'a> Area<'a, Range<f64>> {
+    /// Constructs a new area generator for the points of a series produced
+    /// by [Stack](crate::shape::stack::Stack).  The `y` closure maps stacked
+    /// values to the output range and is applied to the lower bound of each
+    /// point for the baseline and to the upper bound for the topline.  Points
+    /// where either bound is NaN are treated as undefined.
+    ///
+    /// The `x` accessor must still be set, typically from the index.
+    pub fn stacked<ScaleFunc>(y: ScaleFunc) -> Self
+    where
+        ScaleFunc: Fn(f64) -> f64 + 'a,
+    {
+        let y0 = Rc::new(y);
+        let y1 = y0.clone();
+
+        Self::new()
+            .y0(Box::new(move |datum, _i| y0(datum.start)))
+            .y1(Some(Box::new(move |datum, _i| y1(datum.end))))
+            .defined(Box::new(|datum, _i| {
+                !datum.start.is_nan() && !datum.end.is_nan()
+            }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_results() {
+        let mut area = Area::<(f64, f64, f64)>::new()
+            .x(Box::new(|datum, _i| datum.0))
+            .y0(Box::new(|datum, _i| datum.1))
+            .y1(Some(Box::new(|datum, _i| datum.2)));
+
+        {
+            // test.pathEqual(a([[0, 1, 2]]), "M0,2L0,1Z");
+            let data: &[(f64, f64, f64)] = &[(0., 1., 2.)];
+            assert_eq!("M0,2L0,1Z", area.generate(data));
+        }
+
+        {
+            // test.pathEqual(a([[0, 1, 2], [1, 3, 4]]), "M0,2L1,4L1,3L0,1Z");
+            let data: &[(f64, f64, f64)] = &[(0., 1., 2.), (1., 3., 4.)];
+            assert_eq!("M0,2L1,4L1,3L0,1Z", area.generate(data));
+        }
+
+        {
+            // test.pathEqual(a([[0, 1, 2], [1, 3, 4], [2, 5, 6]]), "M0,2L1,4L2,6L2,5L1,3L0,1Z");
+            let data: &[(f64, f64, f64)] = &[(0., 1., 2.), (1., 3., 4.), (2., 5., 6.)];
+            assert_eq!("M0,2L1,4L2,6L2,5L1,3L0,1Z", area.generate(data));
+        }
+    }
+
+    #[test]
+    fn defined_splits_segments() {
+        let mut area = Area::<(f64, f64)>::new()
+            .x(Box::new(|datum, _i| datum.0))
+            .y1(Some(Box::new(|datum, _i| datum.1)))
+            .defined(Box::new(|datum, _i| !datum.1.is_nan()));
+
+        // test.pathEqual(a([[0, 1], [1, 2], [2, NaN], [3, 4], [4, 5]]), "M0,1L1,2L1,0L0,0ZM3,4L4,5L4,0L3,0Z");
+        let data: &[(f64, f64)] = &[(0., 1.), (1., 2.), (2., std::f64::NAN), (3., 4.), (4., 5.)];
+        assert_eq!(
+            "M0,1L1,2L1,0L0,0ZM3,4L4,5L4,0L3,0Z",
+            area.generate(data)
+        );
+    }
+
+    #[test]
+    fn stacked_series() {
+        use crate::shape::stack::StackRow;
+
+        let row: StackRow = vec![0.0..1.0, 1.0..3.0, std::f64::NAN..std::f64::NAN];
+
+        let mut area = Area::stacked(|d| d * 10.0).x(Box::new(|_datum, i| i as f64));
+
+        assert_eq!("M0,10L1,30L1,10L0,0Z", area.generate(&row));
+    }
+}
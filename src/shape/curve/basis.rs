@@ -5,10 +5,8 @@
 //! the first and second points, and to the line between the penultimate and
 //! last points.
 
-use pathfinder_canvas::Path2D;
-use pathfinder_geometry::vector::Vector2F;
-
 use super::{CurveContext, CurveGenerator};
+use crate::path::PathContext;
 
 use std::f64::NAN;
 
@@ -22,14 +20,15 @@ impl CurveBasis {
 }
 
 impl CurveGenerator for CurveBasis {
-    fn context(&self) -> Box<dyn CurveContext> {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
         Box::new(CurveBasisContext {
             line_state: 0,
+            area_line: None,
             x0: NAN,
             x1: NAN,
             y0: NAN,
             y1: NAN,
-            path: Path2D::new(),
+            path,
         })
     }
 
@@ -38,36 +37,44 @@ impl CurveGenerator for CurveBasis {
     }
 }
 
-#[derive(Debug)]
-pub struct CurveBasisContext {
+pub struct CurveBasisContext<'a> {
     line_state: u8,
+    area_line: Option<bool>,
     x0: f64,
     x1: f64,
     y0: f64,
     y1: f64,
-    path: Path2D,
+    path: &'a mut dyn PathContext,
+}
+
+/// Draws the segment of the spline controlled by ⟨x0, y0⟩, ⟨x1, y1⟩ and
+/// ⟨x, y⟩.
+fn basis_point(path: &mut dyn PathContext, x0: f64, y0: f64, x1: f64, y1: f64, x: f64, y: f64) {
+    path.bezier_curve_to(
+        (2.0 * x0 + x1) / 3.0,
+        (2.0 * y0 + y1) / 3.0,
+        (x0 + 2.0 * x1) / 3.0,
+        (y0 + 2.0 * y1) / 3.0,
+        (x0 + 4.0 * x1 + x) / 6.0,
+        (y0 + 4.0 * y1 + y) / 6.0,
+    );
 }
 
-impl CurveBasisContext {
+impl<'a> CurveBasisContext<'a> {
     fn basis_point(&mut self, x: f64, y: f64) {
-        self.path.bezier_curve_to(
-            Vector2F::new(
-                ((2.0 * self.x0 + self.x1) / 3.0) as f32,
-                ((2.0 * self.y0 + self.y1) / 3.0) as f32,
-            ),
-            Vector2F::new(
-                ((self.x0 + 2.0 * self.x1) / 3.0) as f32,
-                ((self.y0 + 2.0 * self.y1) / 3.0) as f32,
-            ),
-            Vector2F::new(
-                ((self.x0 + 4.0 * self.x1 + x) / 6.0) as f32,
-                ((self.y0 + 4.0 * self.y1 + y) / 6.0) as f32,
-            ),
-        );
+        basis_point(self.path, self.x0, self.y0, self.x1, self.y1, x, y);
     }
 }
 
-impl CurveContext for CurveBasisContext {
+impl<'a> CurveContext for CurveBasisContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
     fn start_line(&mut self) {
         self.line_state = 0;
         self.x0 = NAN;
@@ -80,15 +87,19 @@ impl CurveContext for CurveBasisContext {
         match self.line_state {
             0 => {
                 self.line_state += 1;
-                self.path.move_to(Vector2F::new(x as f32, y as f32));
+                if self.area_line == Some(true) {
+                    self.path.line_to(x, y);
+                } else {
+                    self.path.move_to(x, y);
+                }
             }
             1 => self.line_state += 1,
             2 => {
                 self.line_state += 1;
-                self.path.line_to(Vector2F::new(
-                    ((5.0 * self.x0 + self.x1) / 6.0) as f32,
-                    ((5.0 * self.y0 + self.y1) / 6.0) as f32,
-                ));
+                self.path.line_to(
+                    (5.0 * self.x0 + self.x1) / 6.0,
+                    (5.0 * self.y0 + self.y1) / 6.0,
+                );
                 self.basis_point(x, y);
             }
             _ => self.basis_point(x, y),
@@ -106,18 +117,215 @@ impl CurveContext for CurveBasisContext {
         }
 
         if self.line_state == 2 || self.line_state == 3 {
-            self.path
-                .line_to(Vector2F::new(self.x1 as f32, self.y1 as f32));
+            self.path.line_to(self.x1, self.y1);
         }
 
-        if self.line_state == 1 {
+        if self.area_line == Some(true) || (self.area_line.is_none() && self.line_state == 1) {
             self.path.close_path();
         }
+        self.area_line = self.area_line.map(|line| !line);
         self.line_state -= 1;
     }
+}
+
+/// `CurveBasisClosed` produces a closed cubic basis spline.  When a line
+/// segment ends, the first three control points are repeated, producing a
+/// closed loop with C2 continuity.
+#[derive(Debug)]
+pub struct CurveBasisClosed {}
+
+impl CurveBasisClosed {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CurveGenerator for CurveBasisClosed {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveBasisClosedContext {
+            line_state: 0,
+            x0: NAN,
+            x1: NAN,
+            y0: NAN,
+            y1: NAN,
+            first_x: [NAN, NAN, NAN],
+            first_y: [NAN, NAN, NAN],
+            path,
+        })
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+pub struct CurveBasisClosedContext<'a> {
+    line_state: u8,
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+    first_x: [f64; 3],
+    first_y: [f64; 3],
+    path: &'a mut dyn PathContext,
+}
+
+impl<'a> CurveContext for CurveBasisClosedContext<'a> {
+    fn start_area(&mut self) {}
+
+    fn end_area(&mut self) {}
+
+    fn start_line(&mut self) {
+        self.line_state = 0;
+        self.x0 = NAN;
+        self.x1 = NAN;
+        self.y0 = NAN;
+        self.y1 = NAN;
+        self.first_x = [NAN, NAN, NAN];
+        self.first_y = [NAN, NAN, NAN];
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        match self.line_state {
+            0 => {
+                self.line_state += 1;
+                self.first_x[0] = x;
+                self.first_y[0] = y;
+            }
+            1 => {
+                self.line_state += 1;
+                self.first_x[1] = x;
+                self.first_y[1] = y;
+            }
+            2 => {
+                self.line_state += 1;
+                self.first_x[2] = x;
+                self.first_y[2] = y;
+                self.path.move_to(
+                    (self.x0 + 4.0 * self.x1 + x) / 6.0,
+                    (self.y0 + 4.0 * self.y1 + y) / 6.0,
+                );
+            }
+            _ => basis_point(self.path, self.x0, self.y0, self.x1, self.y1, x, y),
+        }
+        self.x0 = self.x1;
+        self.x1 = x;
+        self.y0 = self.y1;
+        self.y1 = y;
+    }
+
+    fn end_line(&mut self) {
+        let (x, y) = (self.first_x, self.first_y);
+
+        match self.line_state {
+            1 => {
+                self.path.move_to(x[0], y[0]);
+                self.path.close_path();
+            }
+            2 => {
+                self.path
+                    .move_to((x[0] + 2.0 * x[1]) / 3.0, (y[0] + 2.0 * y[1]) / 3.0);
+                self.path
+                    .line_to((x[1] + 2.0 * x[0]) / 3.0, (y[1] + 2.0 * y[0]) / 3.0);
+                self.path.close_path();
+            }
+            3 => {
+                self.point(x[0], y[0]);
+                self.point(x[1], y[1]);
+                self.point(x[2], y[2]);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// `CurveBasisOpen` produces a cubic basis spline.  Unlike
+/// [CurveBasis](CurveBasis), the first and last points are not repeated, and
+/// thus the curve typically does not intersect these points.
+#[derive(Debug)]
+pub struct CurveBasisOpen {}
+
+impl CurveBasisOpen {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CurveGenerator for CurveBasisOpen {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveBasisOpenContext {
+            line_state: 0,
+            area_line: None,
+            x0: NAN,
+            x1: NAN,
+            y0: NAN,
+            y1: NAN,
+            path,
+        })
+    }
 
-    fn path(&self) -> &Path2D {
-        &self.path
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+pub struct CurveBasisOpenContext<'a> {
+    line_state: u8,
+    area_line: Option<bool>,
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+    path: &'a mut dyn PathContext,
+}
+
+impl<'a> CurveContext for CurveBasisOpenContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
+    fn start_line(&mut self) {
+        self.line_state = 0;
+        self.x0 = NAN;
+        self.x1 = NAN;
+        self.y0 = NAN;
+        self.y1 = NAN;
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        match self.line_state {
+            0 | 1 => self.line_state += 1,
+            2 => {
+                self.line_state += 1;
+                let x0 = (self.x0 + 4.0 * self.x1 + x) / 6.0;
+                let y0 = (self.y0 + 4.0 * self.y1 + y) / 6.0;
+                if self.area_line == Some(true) {
+                    self.path.line_to(x0, y0);
+                } else {
+                    self.path.move_to(x0, y0);
+                }
+            }
+            3 => {
+                self.line_state += 1;
+                basis_point(self.path, self.x0, self.y0, self.x1, self.y1, x, y);
+            }
+            _ => basis_point(self.path, self.x0, self.y0, self.x1, self.y1, x, y),
+        }
+        self.x0 = self.x1;
+        self.x1 = x;
+        self.y0 = self.y1;
+        self.y1 = y;
+    }
+
+    fn end_line(&mut self) {
+        if self.area_line == Some(true) || (self.area_line.is_none() && self.line_state == 3) {
+            self.path.close_path();
+        }
+        self.area_line = self.area_line.map(|line| !line);
     }
 }
 
@@ -130,6 +338,7 @@ mod tests {
         use crate::shape::line::Line;
 
         let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
             .x(Box::new(|datum, _i| datum.0))
             .y(Box::new(|datum, _i| datum.1))
             .curve(Box::new(CurveBasis::new()));
@@ -137,19 +346,82 @@ mod tests {
         {
             // test.pathEqual(l([[0, 1]]), "M0,1Z");
             let data: &[(f64, f64)] = &[(0., 1.)];
-            assert_eq!("M 0 1 L 0 1 z", line.generate(data));
+            assert_eq!("M0,1Z", line.generate(data));
         }
 
         {
             // test.pathEqual(l([[0, 1], [1, 3]]), "M0,1L1,3");
             let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
-            assert_eq!("M 0 1 L 1 3", line.generate(data));
+            assert_eq!("M0,1L1,3", line.generate(data));
         }
 
         {
             // test.pathEqual(l([[0, 1], [1, 3], [2, 1]]), "M0,1L0.166667,1.333333C0.333333,1.666667,0.666667,2.333333,1,2.333333C1.333333,2.333333,1.666667,1.666667,1.833333,1.333333L2,1");
             let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
-            assert_eq!("M 0 1 L 0.16666667 1.3333334 C 0.33333334 1.6666666 0.6666667 2.3333333 1 2.3333333 C 1.3333334 2.3333333 1.6666666 1.6666666 1.8333334 1.3333334 L 2 1", line.generate(data));
+            assert_eq!("M0,1L0.166667,1.333333C0.333333,1.666667,0.666667,2.333333,1,2.333333C1.333333,2.333333,1.666667,1.666667,1.833333,1.333333L2,1", line.generate(data));
+        }
+    }
+
+    #[test]
+    fn closed_expected_results() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveBasisClosed::new()));
+
+        {
+            // test.pathEqual(l([[0, 1]]), "M0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.)];
+            assert_eq!("M0,1Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3]]), "M0.666667,2.333333L0.333333,1.666667Z");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
+            assert_eq!("M0.666667,2.333333L0.333333,1.666667Z", line.generate(data));
+        }
+
+        {
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
+            assert_eq!(
+                "M1,2.333333C1.333333,2.333333,1.666667,1.666667,1.5,1.333333C1.333333,1,0.666667,1,0.5,1.333333C0.333333,1.666667,0.666667,2.333333,1,2.333333",
+                line.generate(data)
+            );
+        }
+    }
+
+    #[test]
+    fn open_expected_results() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveBasisOpen::new()));
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3]]), "");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
+            assert_eq!("", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1]]), "M1,2.333333Z");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
+            assert_eq!("M1,2.333333Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1], [3, 3]]), "M1,2.333333C1.333333,2.333333,1.666667,1.666667,2,1.666667");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.), (3., 3.)];
+            assert_eq!(
+                "M1,2.333333C1.333333,2.333333,1.666667,1.666667,2,1.666667",
+                line.generate(data)
+            );
         }
     }
 }
@@ -0,0 +1,153 @@
+//! `CurveBundle` produces a straightened cubic basis spline.
+//!
+//! The spline is straightened according to the curve’s beta, which defaults
+//! to 0.85.  This curve is typically used in hierarchical edge bundling to
+//! disambiguate connections, as proposed by Danny Holten in [Hierarchical Edge
+//! Bundles: Visualization of Adjacency Relations in Hierarchical
+//! Data](https://www.win.tue.nl/vis1/home/dholten/papers/bundles_infovis.pdf).
+//! This curve is not suitable for areas.
+
+use super::{CurveBasis, CurveContext, CurveGenerator};
+use crate::path::PathContext;
+
+#[derive(Debug)]
+pub struct CurveBundle {
+    beta: f64,
+}
+
+impl CurveBundle {
+    pub fn new() -> Self {
+        Self { beta: 0.85 }
+    }
+
+    /// Constructs a bundle curve with the given straightening factor in the
+    /// range [0, 1].  A beta of 0 produces a straight line between the first
+    /// and last points, and a beta of 1 produces a basis spline.
+    pub fn with_beta(beta: f64) -> Self {
+        Self { beta }
+    }
+}
+
+impl CurveGenerator for CurveBundle {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        let basis = CurveBasis::new().context(path);
+
+        if self.beta == 1.0 {
+            return basis;
+        }
+
+        Box::new(CurveBundleContext {
+            beta: self.beta,
+            x: vec![],
+            y: vec![],
+            basis,
+        })
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+pub struct CurveBundleContext<'a> {
+    beta: f64,
+    x: Vec<f64>,
+    y: Vec<f64>,
+    basis: Box<dyn CurveContext + 'a>,
+}
+
+impl<'a> CurveContext for CurveBundleContext<'a> {
+    fn start_area(&mut self) {
+        self.basis.start_area();
+    }
+
+    fn end_area(&mut self) {
+        self.basis.end_area();
+    }
+
+    fn start_line(&mut self) {
+        self.x = vec![];
+        self.y = vec![];
+        self.basis.start_line();
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        self.x.push(x);
+        self.y.push(y);
+    }
+
+    fn end_line(&mut self) {
+        let n = self.x.len();
+
+        // A single point can't be straightened and draws nothing.
+        if n > 1 {
+            let j = (n - 1) as f64;
+            let (x0, y0) = (self.x[0], self.y[0]);
+            let dx = self.x[n - 1] - x0;
+            let dy = self.y[n - 1] - y0;
+
+            for i in 0..n {
+                let t = i as f64 / j;
+
+                self.basis.point(
+                    self.beta * self.x[i] + (1.0 - self.beta) * (x0 + t * dx),
+                    self.beta * self.y[i] + (1.0 - self.beta) * (y0 + t * dy),
+                );
+            }
+
+            self.basis.end_line();
+        }
+
+        self.x = vec![];
+        self.y = vec![];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::line::Line;
+
+    fn line<'a>(curve: Box<dyn CurveGenerator>) -> Line<'a, (f64, f64)> {
+        Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(curve)
+    }
+
+    #[test]
+    fn beta_one_is_a_basis_spline() {
+        let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.), (3., 3.)];
+
+        assert_eq!(
+            line(Box::new(CurveBasis::new())).generate(data),
+            line(Box::new(CurveBundle::with_beta(1.0))).generate(data)
+        );
+    }
+
+    #[test]
+    fn beta_straightens_the_spline() {
+        let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
+
+        // The middle point is pulled 15% of the way towards the line between
+        // the first and last points.
+        let straightened: &[(f64, f64)] = &[(0., 1.), (1., 2.7), (2., 1.)];
+        assert_eq!(
+            line(Box::new(CurveBasis::new())).generate(straightened),
+            line(Box::new(CurveBundle::new())).generate(data)
+        );
+
+        assert_eq!(
+            "M0,1L0.166667,1C0.333333,1,0.666667,1,1,1C1.333333,1,1.666667,1,1.833333,1L2,1",
+            line(Box::new(CurveBundle::with_beta(0.0))).generate(data)
+        );
+    }
+
+    #[test]
+    fn single_point_draws_nothing() {
+        let data: &[(f64, f64)] = &[(0., 1.)];
+
+        assert_eq!("", line(Box::new(CurveBundle::new())).generate(data));
+    }
+}
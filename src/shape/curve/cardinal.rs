@@ -3,10 +3,8 @@
 //! The spline is created using the specified control points, with one-sided
 //! differences used for the first and last piece. The default tension is 0.
 
-use pathfinder_canvas::Path2D;
-use pathfinder_geometry::vector::Vector2F;
-
 use super::{CurveContext, CurveGenerator};
+use crate::path::PathContext;
 
 use std::f64::NAN;
 
@@ -26,13 +24,14 @@ impl CurveCardinal {
 }
 
 impl CurveGenerator for CurveCardinal {
-    fn context(&self) -> Box<dyn CurveContext> {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
         Box::new(CurveCardinalContext {
             line_state: 0,
+            area_line: None,
             x: [NAN, NAN, NAN],
             y: [NAN, NAN, NAN],
             tension: self.tension,
-            path: Path2D::new(),
+            path,
         })
     }
 
@@ -41,34 +40,52 @@ impl CurveGenerator for CurveCardinal {
     }
 }
 
-#[derive(Debug)]
-pub struct CurveCardinalContext {
+pub struct CurveCardinalContext<'a> {
     line_state: u8,
+    area_line: Option<bool>,
     x: [f64; 3],
     y: [f64; 3],
     tension: f64,
-    path: Path2D,
+    path: &'a mut dyn PathContext,
 }
 
-impl CurveCardinalContext {
-    fn cardinal_point(&mut self, x: f64, y: f64) {
-        let k = (1.0 - self.tension) / 6.0;
+/// Draws the segment of the spline between the last two of the three
+/// control points, using ⟨x, y⟩ as the next control point.
+fn cardinal_point(
+    path: &mut dyn PathContext,
+    tension: f64,
+    xs: &[f64; 3],
+    ys: &[f64; 3],
+    x: f64,
+    y: f64,
+) {
+    let k = (1.0 - tension) / 6.0;
+
+    path.bezier_curve_to(
+        xs[1] + k * (xs[2] - xs[0]),
+        ys[1] + k * (ys[2] - ys[0]),
+        xs[2] + k * (xs[1] - x),
+        ys[2] + k * (ys[1] - y),
+        xs[2],
+        ys[2],
+    );
+}
 
-        self.path.bezier_curve_to(
-            Vector2F::new(
-                (self.x[1] + k * (self.x[2] - self.x[0])) as f32,
-                (self.y[1] + k * (self.y[2] - self.y[0])) as f32,
-            ),
-            Vector2F::new(
-                (self.x[2] + k * (self.x[1] - x)) as f32,
-                (self.y[2] + k * (self.y[1] - y)) as f32,
-            ),
-            Vector2F::new((self.x[2]) as f32, (self.y[2]) as f32),
-        );
+impl<'a> CurveCardinalContext<'a> {
+    fn cardinal_point(&mut self, x: f64, y: f64) {
+        cardinal_point(self.path, self.tension, &self.x, &self.y, x, y);
     }
 }
 
-impl CurveContext for CurveCardinalContext {
+impl<'a> CurveContext for CurveCardinalContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
     fn start_line(&mut self) {
         self.line_state = 0;
         self.x = [NAN, NAN, NAN];
@@ -79,7 +96,11 @@ impl CurveContext for CurveCardinalContext {
         match self.line_state {
             0 => {
                 self.line_state += 1;
-                self.path.move_to(Vector2F::new(x as f32, y as f32));
+                if self.area_line == Some(true) {
+                    self.path.line_to(x, y);
+                } else {
+                    self.path.move_to(x, y);
+                }
             }
             1 => {
                 self.line_state += 1;
@@ -104,18 +125,220 @@ impl CurveContext for CurveCardinalContext {
     fn end_line(&mut self) {
         match self.line_state {
             3 => self.cardinal_point(self.x[1], self.y[1]),
-            2 => self
-                .path
-                .line_to(Vector2F::new(self.x[2] as f32, self.y[2] as f32)),
-            1 => self.path.close_path(),
+            2 => self.path.line_to(self.x[2], self.y[2]),
+            1 => (),
             _ => unimplemented!(),
         }
 
+        if self.area_line == Some(true) || (self.area_line.is_none() && self.line_state == 1) {
+            self.path.close_path();
+        }
+        self.area_line = self.area_line.map(|line| !line);
         self.line_state -= 1;
     }
+}
+
+/// `CurveCardinalClosed` produces a closed cubic cardinal spline.  When a line
+/// segment ends, the first three control points are repeated, producing a
+/// closed loop.
+#[derive(Debug)]
+pub struct CurveCardinalClosed {
+    tension: f64,
+}
+
+impl CurveCardinalClosed {
+    pub fn new() -> Self {
+        Self { tension: 0.0 }
+    }
+
+    pub fn with_tension(tension: f64) -> Self {
+        Self { tension }
+    }
+}
+
+impl CurveGenerator for CurveCardinalClosed {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveCardinalClosedContext {
+            line_state: 0,
+            x: [NAN, NAN, NAN],
+            y: [NAN, NAN, NAN],
+            first_x: [NAN, NAN, NAN],
+            first_y: [NAN, NAN, NAN],
+            tension: self.tension,
+            path,
+        })
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+pub struct CurveCardinalClosedContext<'a> {
+    line_state: u8,
+    x: [f64; 3],
+    y: [f64; 3],
+    first_x: [f64; 3],
+    first_y: [f64; 3],
+    tension: f64,
+    path: &'a mut dyn PathContext,
+}
+
+impl<'a> CurveContext for CurveCardinalClosedContext<'a> {
+    fn start_area(&mut self) {}
 
-    fn path(&self) -> &Path2D {
-        &self.path
+    fn end_area(&mut self) {}
+
+    fn start_line(&mut self) {
+        self.line_state = 0;
+        self.x = [NAN, NAN, NAN];
+        self.y = [NAN, NAN, NAN];
+        self.first_x = [NAN, NAN, NAN];
+        self.first_y = [NAN, NAN, NAN];
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        match self.line_state {
+            0 => {
+                self.line_state += 1;
+                self.first_x[0] = x;
+                self.first_y[0] = y;
+            }
+            1 => {
+                self.line_state += 1;
+                self.first_x[1] = x;
+                self.first_y[1] = y;
+                self.path.move_to(x, y);
+            }
+            2 => {
+                self.line_state += 1;
+                self.first_x[2] = x;
+                self.first_y[2] = y;
+            }
+            _ => cardinal_point(self.path, self.tension, &self.x, &self.y, x, y),
+        }
+        self.x[0] = self.x[1];
+        self.x[1] = self.x[2];
+        self.x[2] = x;
+
+        self.y[0] = self.y[1];
+        self.y[1] = self.y[2];
+        self.y[2] = y;
+    }
+
+    fn end_line(&mut self) {
+        let (x, y) = (self.first_x, self.first_y);
+
+        match self.line_state {
+            1 => {
+                self.path.move_to(x[0], y[0]);
+                self.path.close_path();
+            }
+            2 => {
+                self.path.line_to(x[0], y[0]);
+                self.path.close_path();
+            }
+            3 => {
+                self.point(x[0], y[0]);
+                self.point(x[1], y[1]);
+                self.point(x[2], y[2]);
+            }
+            _ => (),
+        }
+    }
+}
+
+/// `CurveCardinalOpen` produces a cubic cardinal spline.  Unlike
+/// [CurveCardinal](CurveCardinal), one-sided differences are not used for the
+/// first and last piece, and thus the curve starts at the second point and
+/// ends at the penultimate point.
+#[derive(Debug)]
+pub struct CurveCardinalOpen {
+    tension: f64,
+}
+
+impl CurveCardinalOpen {
+    pub fn new() -> Self {
+        Self { tension: 0.0 }
+    }
+
+    pub fn with_tension(tension: f64) -> Self {
+        Self { tension }
+    }
+}
+
+impl CurveGenerator for CurveCardinalOpen {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveCardinalOpenContext {
+            line_state: 0,
+            area_line: None,
+            x: [NAN, NAN, NAN],
+            y: [NAN, NAN, NAN],
+            tension: self.tension,
+            path,
+        })
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+pub struct CurveCardinalOpenContext<'a> {
+    line_state: u8,
+    area_line: Option<bool>,
+    x: [f64; 3],
+    y: [f64; 3],
+    tension: f64,
+    path: &'a mut dyn PathContext,
+}
+
+impl<'a> CurveContext for CurveCardinalOpenContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
+    fn start_line(&mut self) {
+        self.line_state = 0;
+        self.x = [NAN, NAN, NAN];
+        self.y = [NAN, NAN, NAN];
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        match self.line_state {
+            0 | 1 => self.line_state += 1,
+            2 => {
+                self.line_state += 1;
+                if self.area_line == Some(true) {
+                    self.path.line_to(self.x[2], self.y[2]);
+                } else {
+                    self.path.move_to(self.x[2], self.y[2]);
+                }
+            }
+            3 => {
+                self.line_state += 1;
+                cardinal_point(self.path, self.tension, &self.x, &self.y, x, y);
+            }
+            _ => cardinal_point(self.path, self.tension, &self.x, &self.y, x, y),
+        }
+        self.x[0] = self.x[1];
+        self.x[1] = self.x[2];
+        self.x[2] = x;
+
+        self.y[0] = self.y[1];
+        self.y[1] = self.y[2];
+        self.y[2] = y;
+    }
+
+    fn end_line(&mut self) {
+        if self.area_line == Some(true) || (self.area_line.is_none() && self.line_state == 3) {
+            self.path.close_path();
+        }
+        self.area_line = self.area_line.map(|line| !line);
     }
 }
 
@@ -128,6 +351,7 @@ mod tests {
         use crate::shape::line::Line;
 
         let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
             .x(Box::new(|datum, _i| datum.0))
             .y(Box::new(|datum, _i| datum.1))
             .curve(Box::new(CurveCardinal::new()));
@@ -135,20 +359,20 @@ mod tests {
         {
             // test.pathEqual(l([[0, 1]]), "M0,1Z");
             let data: &[(f64, f64)] = &[(0., 1.)];
-            assert_eq!("M 0 1 L 0 1 z", line.generate(data));
+            assert_eq!("M0,1Z", line.generate(data));
         }
 
         {
             // test.pathEqual(l([[0, 1], [1, 3]]), "M0,1L1,3");
             let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
-            assert_eq!("M 0 1 L 1 3", line.generate(data));
+            assert_eq!("M0,1L1,3", line.generate(data));
         }
 
         {
             // test.pathEqual(l([[0, 1], [1, 3], [2, 1]]), "M0,1C0,1,0.666667,3,1,3C1.333333,3,2,1,2,1");
             let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
             assert_eq!(
-                "M 0 1 C 0 1 0.6666667 3 1 3 C 1.3333334 3 2 1 2 1",
+                "M0,1C0,1,0.666667,3,1,3C1.333333,3,2,1,2,1",
                 line.generate(data)
             );
         }
@@ -157,7 +381,7 @@ mod tests {
             // test.pathEqual(l([[0, 1], [1, 3], [2, 1], [3, 3]]), "M0,1C0,1,0.666667,3,1,3C1.333333,3,1.666667,1,2,1C2.333333,1,3,3,3,3");
             let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.), (3., 3.)];
             assert_eq!(
-                "M 0 1 C 0 1 0.6666667 3 1 3 C 1.3333334 3 1.6666666 1 2 1 C 2.3333333 1 3 3 3 3",
+                "M0,1C0,1,0.666667,3,1,3C1.333333,3,1.666667,1,2,1C2.333333,1,3,3,3,3",
                 line.generate(data)
             );
         }
@@ -168,6 +392,7 @@ mod tests {
         use crate::shape::line::Line;
 
         let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
             .curve(Box::new(CurveCardinal::with_tension(0.5)))
             .x(Box::new(|datum, _i| datum.0))
             .y(Box::new(|datum, _i| datum.1));
@@ -176,9 +401,70 @@ mod tests {
             // test.pathEqual(shape.line().curve(shape.curveCardinal.tension(0.5))([[0, 1], [1, 3], [2, 1], [3, 3]]), "M0,1C0,1,0.833333,3,1,3C1.166667,3,1.833333,1,2,1C2.166667,1,3,3,3,3");
             let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.), (3., 3.)];
             assert_eq!(
-                "M 0 1 C 0 1 0.8333333 3 1 3 C 1.1666666 3 1.8333334 1 2 1 C 2.1666667 1 3 3 3 3",
+                "M0,1C0,1,0.833333,3,1,3C1.166667,3,1.833333,1,2,1C2.166667,1,3,3,3,3",
                 line.generate(data)
             );
         }
     }
+
+    #[test]
+    fn closed_expected_results() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveCardinalClosed::new()));
+
+        {
+            // test.pathEqual(l([[0, 1]]), "M0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.)];
+            assert_eq!("M0,1Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3]]), "M1,3L0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
+            assert_eq!("M1,3L0,1Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1]]), "M1,3C1.333333,3,2.166667,1.333333,2,1C1.833333,0.666667,0.166667,0.666667,0,1C-0.166667,1.333333,0.666667,3,1,3");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
+            assert_eq!(
+                "M1,3C1.333333,3,2.166667,1.333333,2,1C1.833333,0.666667,0.166667,0.666667,0,1C-0.166667,1.333333,0.666667,3,1,3",
+                line.generate(data)
+            );
+        }
+    }
+
+    #[test]
+    fn open_expected_results() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveCardinalOpen::new()));
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3]]), "");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
+            assert_eq!("", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1]]), "M1,3Z");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
+            assert_eq!("M1,3Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1], [3, 3]]), "M1,3C1.333333,3,1.666667,1,2,1");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.), (3., 3.)];
+            assert_eq!("M1,3C1.333333,3,1.666667,1,2,1", line.generate(data));
+        }
+    }
 }
@@ -0,0 +1,504 @@
+//! `CurveCatmullRom` produces a cubic Catmull–Rom spline using the specified
+//! control points and the parameter alpha.
+//!
+//! As proposed by Yuksel et al. in [On the Parameterization of Catmull–Rom
+//! Curves](http://www.cemyuksel.com/research/catmullrom_param/), the
+//! centripetal parameterization (alpha = 0.5) avoids the cusps and
+//! self-intersections of the uniform parameterization (alpha = 0).  The
+//! chordal parameterization uses an alpha of 1.
+
+use super::{CurveCardinal, CurveCardinalClosed, CurveCardinalOpen, CurveContext, CurveGenerator};
+use crate::path::PathContext;
+
+use std::f64::NAN;
+
+const EPSILON: f64 = 1e-12;
+
+#[derive(Debug)]
+pub struct CurveCatmullRom {
+    alpha: f64,
+}
+
+impl CurveCatmullRom {
+    pub fn new() -> Self {
+        Self { alpha: 0.5 }
+    }
+
+    pub fn with_alpha(alpha: f64) -> Self {
+        Self { alpha }
+    }
+}
+
+impl CurveGenerator for CurveCatmullRom {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        // The uniform parameterization is a cardinal spline with no tension.
+        if self.alpha == 0.0 {
+            return CurveCardinal::new().context(path);
+        }
+
+        Box::new(CurveCatmullRomContext {
+            line_state: 0,
+            area_line: None,
+            spline: CatmullRomSpline::new(self.alpha),
+            path,
+        })
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+/// `CurveCatmullRomClosed` produces a closed cubic Catmull–Rom spline; when a
+/// line segment ends the first three control points are repeated, producing
+/// a closed loop.
+#[derive(Debug)]
+pub struct CurveCatmullRomClosed {
+    alpha: f64,
+}
+
+impl CurveCatmullRomClosed {
+    pub fn new() -> Self {
+        Self { alpha: 0.5 }
+    }
+
+    pub fn with_alpha(alpha: f64) -> Self {
+        Self { alpha }
+    }
+}
+
+impl CurveGenerator for CurveCatmullRomClosed {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        if self.alpha == 0.0 {
+            return CurveCardinalClosed::new().context(path);
+        }
+
+        Box::new(CurveCatmullRomClosedContext {
+            line_state: 0,
+            x: [NAN, NAN, NAN],
+            y: [NAN, NAN, NAN],
+            spline: CatmullRomSpline::new(self.alpha),
+            path,
+        })
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+/// `CurveCatmullRomOpen` produces a cubic Catmull–Rom spline that does not
+/// pass through the first and last control points.
+#[derive(Debug)]
+pub struct CurveCatmullRomOpen {
+    alpha: f64,
+}
+
+impl CurveCatmullRomOpen {
+    pub fn new() -> Self {
+        Self { alpha: 0.5 }
+    }
+
+    pub fn with_alpha(alpha: f64) -> Self {
+        Self { alpha }
+    }
+}
+
+impl CurveGenerator for CurveCatmullRomOpen {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        if self.alpha == 0.0 {
+            return CurveCardinalOpen::new().context(path);
+        }
+
+        Box::new(CurveCatmullRomOpenContext {
+            line_state: 0,
+            area_line: None,
+            spline: CatmullRomSpline::new(self.alpha),
+            path,
+        })
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+/// The last three control points and the parameterized distances between
+/// them, shared by each of the Catmull–Rom contexts.
+#[derive(Debug)]
+struct CatmullRomSpline {
+    alpha: f64,
+    x: [f64; 3],
+    y: [f64; 3],
+    l01_a: f64,
+    l12_a: f64,
+    l23_a: f64,
+    l01_2a: f64,
+    l12_2a: f64,
+    l23_2a: f64,
+}
+
+impl CatmullRomSpline {
+    fn new(alpha: f64) -> Self {
+        Self {
+            alpha,
+            x: [NAN, NAN, NAN],
+            y: [NAN, NAN, NAN],
+            l01_a: 0.0,
+            l12_a: 0.0,
+            l23_a: 0.0,
+            l01_2a: 0.0,
+            l12_2a: 0.0,
+            l23_2a: 0.0,
+        }
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new(self.alpha);
+    }
+
+    /// Computes the parameterized distance from the last control point to the
+    /// given point.
+    fn measure(&mut self, x: f64, y: f64) {
+        let x23 = self.x[2] - x;
+        let y23 = self.y[2] - y;
+
+        self.l23_2a = (x23 * x23 + y23 * y23).powf(self.alpha);
+        self.l23_a = self.l23_2a.sqrt();
+    }
+
+    /// Pushes the given point onto the control points.
+    fn shift(&mut self, x: f64, y: f64) {
+        self.l01_a = self.l12_a;
+        self.l12_a = self.l23_a;
+        self.l01_2a = self.l12_2a;
+        self.l12_2a = self.l23_2a;
+
+        self.x[0] = self.x[1];
+        self.x[1] = self.x[2];
+        self.x[2] = x;
+
+        self.y[0] = self.y[1];
+        self.y[1] = self.y[2];
+        self.y[2] = y;
+    }
+
+    /// Draws the segment between the two most recent control points, using
+    /// the given point as the next control point.
+    fn point(&self, path: &mut dyn PathContext, x: f64, y: f64) {
+        let mut x1 = self.x[1];
+        let mut y1 = self.y[1];
+        let mut x2 = self.x[2];
+        let mut y2 = self.y[2];
+
+        if self.l01_a > EPSILON {
+            let a = 2.0 * self.l01_2a + 3.0 * self.l01_a * self.l12_a + self.l12_2a;
+            let n = 3.0 * self.l01_a * (self.l01_a + self.l12_a);
+            x1 = (x1 * a - self.x[0] * self.l12_2a + self.x[2] * self.l01_2a) / n;
+            y1 = (y1 * a - self.y[0] * self.l12_2a + self.y[2] * self.l01_2a) / n;
+        }
+
+        if self.l23_a > EPSILON {
+            let b = 2.0 * self.l23_2a + 3.0 * self.l23_a * self.l12_a + self.l12_2a;
+            let m = 3.0 * self.l23_a * (self.l23_a + self.l12_a);
+            x2 = (x2 * b + self.x[1] * self.l23_2a - x * self.l12_2a) / m;
+            y2 = (y2 * b + self.y[1] * self.l23_2a - y * self.l12_2a) / m;
+        }
+
+        path.bezier_curve_to(x1, y1, x2, y2, self.x[2], self.y[2]);
+    }
+}
+
+pub struct CurveCatmullRomContext<'a> {
+    line_state: u8,
+    area_line: Option<bool>,
+    spline: CatmullRomSpline,
+    path: &'a mut dyn PathContext,
+}
+
+impl<'a> CurveContext for CurveCatmullRomContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
+    fn start_line(&mut self) {
+        self.line_state = 0;
+        self.spline.reset();
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        if self.line_state > 0 {
+            self.spline.measure(x, y);
+        }
+
+        match self.line_state {
+            0 => {
+                self.line_state += 1;
+                if self.area_line == Some(true) {
+                    self.path.line_to(x, y);
+                } else {
+                    self.path.move_to(x, y);
+                }
+            }
+            1 => self.line_state += 1,
+            2 => {
+                self.line_state += 1;
+                self.spline.point(self.path, x, y);
+            }
+            _ => self.spline.point(self.path, x, y),
+        }
+
+        self.spline.shift(x, y);
+    }
+
+    fn end_line(&mut self) {
+        match self.line_state {
+            2 => self.path.line_to(self.spline.x[2], self.spline.y[2]),
+            3 => self.point(self.spline.x[2], self.spline.y[2]),
+            _ => (),
+        }
+
+        if self.area_line == Some(true) || (self.area_line.is_none() && self.line_state == 1) {
+            self.path.close_path();
+        }
+        self.area_line = self.area_line.map(|line| !line);
+    }
+}
+
+pub struct CurveCatmullRomClosedContext<'a> {
+    line_state: u8,
+    x: [f64; 3],
+    y: [f64; 3],
+    spline: CatmullRomSpline,
+    path: &'a mut dyn PathContext,
+}
+
+impl<'a> CurveContext for CurveCatmullRomClosedContext<'a> {
+    fn start_area(&mut self) {}
+
+    fn end_area(&mut self) {}
+
+    fn start_line(&mut self) {
+        self.line_state = 0;
+        self.x = [NAN, NAN, NAN];
+        self.y = [NAN, NAN, NAN];
+        self.spline.reset();
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        if self.line_state > 0 {
+            self.spline.measure(x, y);
+        }
+
+        match self.line_state {
+            0 => {
+                self.line_state += 1;
+                self.x[0] = x;
+                self.y[0] = y;
+            }
+            1 => {
+                self.line_state += 1;
+                self.x[1] = x;
+                self.y[1] = y;
+                self.path.move_to(x, y);
+            }
+            2 => {
+                self.line_state += 1;
+                self.x[2] = x;
+                self.y[2] = y;
+            }
+            _ => self.spline.point(self.path, x, y),
+        }
+
+        self.spline.shift(x, y);
+    }
+
+    fn end_line(&mut self) {
+        match self.line_state {
+            1 => {
+                self.path.move_to(self.x[0], self.y[0]);
+                self.path.close_path();
+            }
+            2 => {
+                self.path.line_to(self.x[0], self.y[0]);
+                self.path.close_path();
+            }
+            3 => {
+                self.point(self.x[0], self.y[0]);
+                self.point(self.x[1], self.y[1]);
+                self.point(self.x[2], self.y[2]);
+            }
+            _ => (),
+        }
+    }
+}
+
+pub struct CurveCatmullRomOpenContext<'a> {
+    line_state: u8,
+    area_line: Option<bool>,
+    spline: CatmullRomSpline,
+    path: &'a mut dyn PathContext,
+}
+
+impl<'a> CurveContext for CurveCatmullRomOpenContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
+    fn start_line(&mut self) {
+        self.line_state = 0;
+        self.spline.reset();
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        if self.line_state > 0 {
+            self.spline.measure(x, y);
+        }
+
+        match self.line_state {
+            0 | 1 => self.line_state += 1,
+            2 => {
+                self.line_state += 1;
+                if self.area_line == Some(true) {
+                    self.path.line_to(self.spline.x[2], self.spline.y[2]);
+                } else {
+                    self.path.move_to(self.spline.x[2], self.spline.y[2]);
+                }
+            }
+            3 => {
+                self.line_state += 1;
+                self.spline.point(self.path, x, y);
+            }
+            _ => self.spline.point(self.path, x, y),
+        }
+
+        self.spline.shift(x, y);
+    }
+
+    fn end_line(&mut self) {
+        if self.area_line == Some(true) || (self.area_line.is_none() && self.line_state == 3) {
+            self.path.close_path();
+        }
+        self.area_line = self.area_line.map(|line| !line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shape::line::Line;
+
+    fn line<'a>(curve: Box<dyn CurveGenerator>) -> Line<'a, (f64, f64)> {
+        Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(curve)
+    }
+
+    #[test]
+    fn expected_results() {
+        let mut line = line(Box::new(CurveCatmullRom::new()));
+
+        {
+            // test.pathEqual(l([[0, 1]]), "M0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.)];
+            assert_eq!("M0,1Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3]]), "M0,1L1,3");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
+            assert_eq!("M0,1L1,3", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1], [3, 3]]), "M0,1C0,1,0.666667,3,1,3C1.333333,3,1.666667,1,2,1C2.333333,1,3,3,3,3");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.), (3., 3.)];
+            assert_eq!(
+                "M0,1C0,1,0.666667,3,1,3C1.333333,3,1.666667,1,2,1C2.333333,1,3,3,3,3",
+                line.generate(data)
+            );
+        }
+    }
+
+    #[test]
+    fn handles_coincident_points() {
+        let mut line = line(Box::new(CurveCatmullRom::new()));
+
+        // Coincident points have no length and mustn't produce NaN control points.
+        let data: &[(f64, f64)] = &[(0., 1.), (0., 1.), (1., 3.), (2., 1.)];
+        assert_eq!(
+            "M0,1C0,1,0,1,0,1C0,1,0.666667,3,1,3C1.333333,3,2,1,2,1",
+            line.generate(data)
+        );
+    }
+
+    #[test]
+    fn zero_alpha_is_a_uniform_cardinal_spline() {
+        let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (4., 1.), (4.5, 3.), (9., 0.)];
+
+        assert_eq!(
+            line(Box::new(CurveCardinal::new())).generate(data),
+            line(Box::new(CurveCatmullRom::with_alpha(0.0))).generate(data)
+        );
+    }
+
+    #[test]
+    fn closed_expected_results() {
+        let mut line = line(Box::new(CurveCatmullRomClosed::new()));
+
+        {
+            // test.pathEqual(l([[0, 1]]), "M0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.)];
+            assert_eq!("M0,1Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3]]), "M1,3L0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
+            assert_eq!("M1,3L0,1Z", line.generate(data));
+        }
+
+        {
+            // A square has equal distances between points, so the
+            // centripetal spline is a uniform one.
+            let data: &[(f64, f64)] = &[(0., 0.), (1., 0.), (1., 1.), (0., 1.)];
+            assert_eq!(
+                "M1,0C1.166667,0.166667,1.166667,0.833333,1,1C0.833333,1.166667,0.166667,1.166667,0,1C-0.166667,0.833333,-0.166667,0.166667,0,0C0.166667,-0.166667,0.833333,-0.166667,1,0",
+                line.generate(data)
+            );
+        }
+    }
+
+    #[test]
+    fn open_expected_results() {
+        let mut line = line(Box::new(CurveCatmullRomOpen::new()));
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3]]), "");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
+            assert_eq!("", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1]]), "M1,3Z");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
+            assert_eq!("M1,3Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1], [3, 3]]), "M1,3C1.333333,3,1.666667,1,2,1");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.), (3., 3.)];
+            assert_eq!("M1,3C1.333333,3,1.666667,1,2,1", line.generate(data));
+        }
+    }
+}
@@ -1,9 +1,7 @@
 //! `CurveLinear` produces a polyline through the specified points.
 
-use pathfinder_canvas::Path2D;
-use pathfinder_geometry::vector::Vector2F;
-
 use super::{CurveContext, CurveGenerator};
+use crate::path::PathContext;
 
 #[derive(Debug)]
 pub struct CurveLinear {}
@@ -15,10 +13,11 @@ impl CurveLinear {
 }
 
 impl CurveGenerator for CurveLinear {
-    fn context(&self) -> Box<dyn CurveContext> {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
         Box::new(CurveLinearContext {
             line_state: 0,
-            path: Path2D::new(),
+            area_line: None,
+            path,
         })
     }
 
@@ -27,13 +26,21 @@ impl CurveGenerator for CurveLinear {
     }
 }
 
-#[derive(Debug)]
-pub struct CurveLinearContext {
+pub struct CurveLinearContext<'a> {
     line_state: u8,
-    path: Path2D,
+    area_line: Option<bool>,
+    path: &'a mut dyn PathContext,
 }
 
-impl CurveContext for CurveLinearContext {
+impl<'a> CurveContext for CurveLinearContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
     fn start_line(&mut self) {
         self.line_state = 0;
     }
@@ -42,28 +49,84 @@ impl CurveContext for CurveLinearContext {
         match self.line_state {
             0 => {
                 self.line_state += 1;
-                self.path.move_to(Vector2F::new(x as f32, y as f32));
+                if self.area_line == Some(true) {
+                    self.path.line_to(x, y);
+                } else {
+                    self.path.move_to(x, y);
+                }
             }
             1 => {
                 self.line_state += 1;
-                self.path.line_to(Vector2F::new(x as f32, y as f32));
+                self.path.line_to(x, y);
             }
-            _ => self.path.line_to(Vector2F::new(x as f32, y as f32)),
+            _ => self.path.line_to(x, y),
         }
     }
 
     fn end_line(&mut self) {
-        if self.line_state == 1 {
+        if self.area_line == Some(true) || (self.area_line.is_none() && self.line_state == 1) {
             self.path.close_path();
         }
+        self.area_line = self.area_line.map(|line| !line);
 
         if self.line_state > 0 {
             self.line_state -= 1;
         }
     }
+}
+
+/// `CurveLinearClosed` produces a closed polyline through the specified
+/// points by repeating the first point when the line segment ends.
+#[derive(Debug)]
+pub struct CurveLinearClosed {}
 
-    fn path(&self) -> &Path2D {
-        &self.path
+impl CurveLinearClosed {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CurveGenerator for CurveLinearClosed {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveLinearClosedContext {
+            line_state: 0,
+            path,
+        })
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+pub struct CurveLinearClosedContext<'a> {
+    line_state: u8,
+    path: &'a mut dyn PathContext,
+}
+
+impl<'a> CurveContext for CurveLinearClosedContext<'a> {
+    fn start_area(&mut self) {}
+
+    fn end_area(&mut self) {}
+
+    fn start_line(&mut self) {
+        self.line_state = 0;
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        match self.line_state {
+            0 => {
+                self.line_state += 1;
+                self.path.move_to(x, y);
+            }
+            _ => self.path.line_to(x, y),
+        }
+    }
+
+    fn end_line(&mut self) {
+        if self.line_state > 0 {
+            self.path.close_path();
+        }
     }
 }
 
@@ -81,19 +144,48 @@ mod tests {
         {
             // test.pathEqual(l([[0, 1]]), "M0,1Z");
             let data: &[(f64, f64)] = &[(0., 1.)];
-            assert_eq!("M 0 1 L 0 1 z", line.generate(data));
+            assert_eq!("M0,1Z", line.generate(data));
         }
 
         {
             // test.pathEqual(l([[0, 1], [2, 3]]), "M0,1L2,3");
             let data: &[(f64, f64)] = &[(0., 1.), (2., 3.)];
-            assert_eq!("M 0 1 L 2 3", line.generate(data));
+            assert_eq!("M0,1L2,3", line.generate(data));
         }
 
         {
             // test.pathEqual(l([[0, 1], [2, 3], [4, 5]]), "M0,1L2,3L4,5");
             let data: &[(f64, f64)] = &[(0., 1.), (2., 3.), (4., 5.)];
-            assert_eq!("M 0 1 L 2 3 L 4 5", line.generate(data));
+            assert_eq!("M0,1L2,3L4,5", line.generate(data));
+        }
+    }
+
+    #[test]
+    fn closed_expected_results() {
+        use super::CurveLinearClosed;
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveLinearClosed::new()));
+
+        {
+            // test.pathEqual(l([[0, 1]]), "M0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.)];
+            assert_eq!("M0,1Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [2, 3]]), "M0,1L2,3Z");
+            let data: &[(f64, f64)] = &[(0., 1.), (2., 3.)];
+            assert_eq!("M0,1L2,3Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [2, 3], [4, 5]]), "M0,1L2,3L4,5Z");
+            let data: &[(f64, f64)] = &[(0., 1.), (2., 3.), (4., 5.)];
+            assert_eq!("M0,1L2,3L4,5Z", line.generate(data));
         }
     }
 }
@@ -1,27 +1,39 @@
 //! Curves interpolate discrete data typically for the purpose of drawing lines.
 
-use pathfinder_canvas::Path2D;
+use crate::path::PathContext;
 
 pub trait CurveGenerator {
-    fn context(&self) -> Box<dyn CurveContext>;
+    /// Returns a new curve context that draws into the given path context.
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a>;
     fn size_hint(&self, points_count: usize) -> usize;
 }
 
 pub trait CurveContext {
+    /// Indicates the start of an area.  An area consists of two lines: the
+    /// topline followed by the baseline in reverse order.  The second line
+    /// continues the first and the area is closed when it ends.
+    fn start_area(&mut self);
+    /// Indicates the end of an area.
+    fn end_area(&mut self);
     fn start_line(&mut self);
     fn point(&mut self, x: f64, y: f64);
     fn end_line(&mut self);
-    fn path(&self) -> &Path2D;
 }
 
 mod basis;
+mod bundle;
 mod cardinal;
+mod catmull_rom;
 mod linear;
+mod monotone;
 mod natural;
 mod step;
 
 pub use basis::*;
+pub use bundle::*;
 pub use cardinal::*;
+pub use catmull_rom::*;
 pub use linear::*;
+pub use monotone::*;
 pub use natural::*;
 pub use step::*;
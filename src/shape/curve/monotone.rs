@@ -0,0 +1,396 @@
+//! `CurveMonotoneX` and `CurveMonotoneY` produce cubic splines that preserve
+//! monotonicity.
+//!
+//! `CurveMonotoneX` assumes monotonicity in x and `CurveMonotoneY` assumes
+//! monotonicity in y.  Neither curve overshoots the data, as described in
+//! [A simple method for monotonic interpolation in one
+//! dimension](https://ui.adsabs.harvard.edu/abs/1990A%26A...239..443S) by
+//! Steffen.
+
+use super::{CurveContext, CurveGenerator};
+use crate::path::PathContext;
+
+use std::f64::NAN;
+
+#[derive(Debug)]
+pub struct CurveMonotoneX {}
+
+impl CurveMonotoneX {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CurveGenerator for CurveMonotoneX {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveMonotoneContext::new(path, false))
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+#[derive(Debug)]
+pub struct CurveMonotoneY {}
+
+impl CurveMonotoneY {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CurveGenerator for CurveMonotoneY {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveMonotoneContext::new(path, true))
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+/// Shared context for both monotone curves.  `CurveMonotoneY` is computed as
+/// `CurveMonotoneX` with the x and y coordinates swapped on the way in and on
+/// the way out.
+pub struct CurveMonotoneContext<'a> {
+    line_state: u8,
+    area_line: Option<bool>,
+    reflect: bool,
+    x0: f64,
+    x1: f64,
+    y0: f64,
+    y1: f64,
+    t0: f64,
+    path: &'a mut dyn PathContext,
+}
+
+fn sign(x: f64) -> f64 {
+    if x < 0.0 {
+        -1.0
+    } else {
+        1.0
+    }
+}
+
+impl<'a> CurveMonotoneContext<'a> {
+    fn new(path: &'a mut dyn PathContext, reflect: bool) -> Self {
+        Self {
+            line_state: 0,
+            area_line: None,
+            reflect,
+            x0: NAN,
+            x1: NAN,
+            y0: NAN,
+            y1: NAN,
+            t0: NAN,
+            path,
+        }
+    }
+
+    fn move_to(&mut self, x: f64, y: f64) {
+        match self.reflect {
+            true => self.path.move_to(y, x),
+            false => self.path.move_to(x, y),
+        }
+    }
+
+    fn line_to(&mut self, x: f64, y: f64) {
+        match self.reflect {
+            true => self.path.line_to(y, x),
+            false => self.path.line_to(x, y),
+        }
+    }
+
+    /// Calculates the slope of the tangent at the middle of three points
+    /// (x0, x1 and the given point) according to Steffen.  Where the
+    /// intervals have zero width the slope takes the sign of the other
+    /// interval, as d3 does.
+    fn slope3(&self, x2: f64, y2: f64) -> f64 {
+        let h0 = self.x1 - self.x0;
+        let h1 = x2 - self.x1;
+        let d0 = if h0 != 0.0 {
+            h0
+        } else if h1 < 0.0 {
+            -0.0
+        } else {
+            0.0
+        };
+        let d1 = if h1 != 0.0 {
+            h1
+        } else if h0 < 0.0 {
+            -0.0
+        } else {
+            0.0
+        };
+        let s0 = (self.y1 - self.y0) / d0;
+        let s1 = (y2 - self.y1) / d1;
+        let p = (s0 * h1 + s1 * h0) / (h0 + h1);
+
+        // f64::min ignores NaN where d3's Math.min propagates it, so check
+        // the operands before taking the minimum.
+        if s0.is_nan() || s1.is_nan() || p.is_nan() {
+            return 0.0;
+        }
+
+        let slope = (sign(s0) + sign(s1)) * s0.abs().min(s1.abs()).min(0.5 * p.abs());
+
+        if slope.is_nan() {
+            0.0
+        } else {
+            slope
+        }
+    }
+
+    /// Calculates a one-sided slope for the end points given the slope of the
+    /// adjacent tangent.
+    fn slope2(&self, t: f64) -> f64 {
+        let h = self.x1 - self.x0;
+
+        if h != 0.0 {
+            (3.0 * (self.y1 - self.y0) / h - t) / 2.0
+        } else {
+            t
+        }
+    }
+
+    /// Draws a Hermite segment from (x0, y0) to (x1, y1) with the tangents t0
+    /// and t1, expressed as a cubic Bézier curve.
+    fn monotone_point(&mut self, t0: f64, t1: f64) {
+        let dx = (self.x1 - self.x0) / 3.0;
+        let (x1, y1, x2, y2, x, y) = (
+            self.x0 + dx,
+            self.y0 + dx * t0,
+            self.x1 - dx,
+            self.y1 - dx * t1,
+            self.x1,
+            self.y1,
+        );
+
+        match self.reflect {
+            true => self.path.bezier_curve_to(y1, x1, y2, x2, y, x),
+            false => self.path.bezier_curve_to(x1, y1, x2, y2, x, y),
+        }
+    }
+}
+
+impl<'a> CurveContext for CurveMonotoneContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
+    fn start_line(&mut self) {
+        self.line_state = 0;
+        self.x0 = NAN;
+        self.x1 = NAN;
+        self.y0 = NAN;
+        self.y1 = NAN;
+        self.t0 = NAN;
+    }
+
+    fn point(&mut self, x: f64, y: f64) {
+        let (x, y) = match self.reflect {
+            true => (y, x),
+            false => (x, y),
+        };
+
+        // Ignore coincident points.
+        if x == self.x1 && y == self.y1 {
+            return;
+        }
+
+        let mut t1 = NAN;
+
+        match self.line_state {
+            0 => {
+                self.line_state += 1;
+                if self.area_line == Some(true) {
+                    self.line_to(x, y);
+                } else {
+                    self.move_to(x, y);
+                }
+            }
+            1 => self.line_state += 1,
+            2 => {
+                self.line_state += 1;
+                t1 = self.slope3(x, y);
+                self.monotone_point(self.slope2(t1), t1);
+            }
+            _ => {
+                t1 = self.slope3(x, y);
+                self.monotone_point(self.t0, t1);
+            }
+        }
+
+        self.x0 = self.x1;
+        self.x1 = x;
+        self.y0 = self.y1;
+        self.y1 = y;
+        self.t0 = t1;
+    }
+
+    fn end_line(&mut self) {
+        match self.line_state {
+            2 => self.line_to(self.x1, self.y1),
+            3 => self.monotone_point(self.t0, self.slope2(self.t0)),
+            _ => (),
+        }
+
+        if self.area_line == Some(true) || (self.area_line.is_none() && self.line_state == 1) {
+            self.path.close_path();
+        }
+        self.area_line = self.area_line.map(|line| !line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monotone_x_expected_results() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveMonotoneX::new()));
+
+        {
+            // test.pathEqual(l([[0, 1]]), "M0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.)];
+            assert_eq!("M0,1Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3]]), "M0,1L1,3");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
+            assert_eq!("M0,1L1,3", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1]]), "M0,1C0.333333,2,0.666667,3,1,3C1.333333,3,1.666667,2,2,1");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
+            assert_eq!(
+                "M0,1C0.333333,2,0.666667,3,1,3C1.333333,3,1.666667,2,2,1",
+                line.generate(data)
+            );
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [1, 3], [2, 1], [3, 3]]), "M0,1C0.333333,2,0.666667,3,1,3C1.333333,3,1.666667,1,2,1C2.333333,1,2.666667,2,3,3");
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.), (3., 3.)];
+            assert_eq!(
+                "M0,1C0.333333,2,0.666667,3,1,3C1.333333,3,1.666667,1,2,1C2.333333,1,2.666667,2,3,3",
+                line.generate(data)
+            );
+        }
+    }
+
+    #[test]
+    fn monotone_x_vertical_runs_and_reversals_have_zero_slope() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveMonotoneX::new()));
+
+        {
+            let data: &[(f64, f64)] = &[(1., 0.), (1., 1.), (1., 2.)];
+            assert_eq!("M1,0C1,0,1,1,1,1C1,1,1,2,1,2", line.generate(data));
+        }
+
+        {
+            let data: &[(f64, f64)] = &[(0., 0.), (1., 1.), (0., 0.)];
+            assert_eq!(
+                "M0,0C0.333333,0.5,0.666667,1,1,1C0.666667,1,0.333333,0.5,0,0",
+                line.generate(data)
+            );
+        }
+    }
+
+    #[test]
+    fn monotone_x_ignores_coincident_points() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveMonotoneX::new()));
+
+        let data: &[(f64, f64)] = &[(0., 200.), (100., 150.), (200., 100.), (300., 50.)];
+        let coincident: &[(f64, f64)] = &[
+            (0., 200.),
+            (0., 200.),
+            (100., 150.),
+            (200., 100.),
+            (200., 100.),
+            (300., 50.),
+        ];
+
+        assert_eq!(line.generate(data), line.generate(coincident));
+    }
+
+    #[test]
+    fn monotone_y_expected_results() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveMonotoneY::new()));
+
+        {
+            // test.pathEqual(l([[1, 0]]), "M1,0Z");
+            let data: &[(f64, f64)] = &[(1., 0.)];
+            assert_eq!("M1,0Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[1, 0], [3, 1]]), "M1,0L3,1");
+            let data: &[(f64, f64)] = &[(1., 0.), (3., 1.)];
+            assert_eq!("M1,0L3,1", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[1, 0], [3, 1], [1, 2]]), "M1,0C2,0.333333,3,0.666667,3,1C3,1.333333,2,1.666667,1,2");
+            let data: &[(f64, f64)] = &[(1., 0.), (3., 1.), (1., 2.)];
+            assert_eq!(
+                "M1,0C2,0.333333,3,0.666667,3,1C3,1.333333,2,1.666667,1,2",
+                line.generate(data)
+            );
+        }
+    }
+
+    #[test]
+    fn monotone_y_horizontal_runs_and_reversals_have_zero_slope() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveMonotoneY::new()));
+
+        {
+            let data: &[(f64, f64)] = &[(0., 1.), (1., 1.), (2., 1.)];
+            assert_eq!("M0,1C0,1,1,1,1,1C1,1,2,1,2,1", line.generate(data));
+        }
+
+        {
+            let data: &[(f64, f64)] = &[(0., 0.), (1., 1.), (0., 0.)];
+            assert_eq!(
+                "M0,0C0.5,0.333333,1,0.666667,1,1C1,0.666667,0.5,0.333333,0,0",
+                line.generate(data)
+            );
+        }
+    }
+}
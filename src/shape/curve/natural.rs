@@ -1,10 +1,8 @@
 //! `CurveNatural` produces a natural cubic spline with the second derivative of
 //! the spline set to zero at the endpoints.
 
-use pathfinder_canvas::Path2D;
-use pathfinder_geometry::vector::Vector2F;
-
 use super::{CurveContext, CurveGenerator};
+use crate::path::PathContext;
 
 #[derive(Debug)]
 pub struct CurveNatural {}
@@ -16,11 +14,12 @@ impl CurveNatural {
 }
 
 impl CurveGenerator for CurveNatural {
-    fn context(&self) -> Box<dyn CurveContext> {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
         Box::new(CurveNaturalContext {
+            area_line: None,
             x: vec![],
             y: vec![],
-            path: Path2D::new(),
+            path,
         })
     }
 
@@ -29,15 +28,15 @@ impl CurveGenerator for CurveNatural {
     }
 }
 
-#[derive(Debug)]
-pub struct CurveNaturalContext {
+pub struct CurveNaturalContext<'a> {
+    area_line: Option<bool>,
     x: Vec<f64>,
     y: Vec<f64>,
-    path: Path2D,
+    path: &'a mut dyn PathContext,
 }
 
 // See https://www.particleincell.com/2012/bezier-splines/ for derivation.
-impl CurveNaturalContext {
+impl<'a> CurveNaturalContext<'a> {
     fn control_points(x: &[f64]) -> (Vec<f64>, Vec<f64>) {
         let n = x.len() - 1;
 
@@ -84,7 +83,15 @@ impl CurveNaturalContext {
     }
 }
 
-impl CurveContext for CurveNaturalContext {
+impl<'a> CurveContext for CurveNaturalContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
     fn start_line(&mut self) {
         self.x = vec![];
         self.y = vec![];
@@ -99,33 +106,31 @@ impl CurveContext for CurveNaturalContext {
         let n = self.x.len();
 
         if n > 0 {
-            self.path
-                .move_to(Vector2F::new(self.x[0] as f32, self.y[0] as f32));
+            if self.area_line == Some(true) {
+                self.path.line_to(self.x[0], self.y[0]);
+            } else {
+                self.path.move_to(self.x[0], self.y[0]);
+            }
             if n == 2 {
-                self.path
-                    .line_to(Vector2F::new(self.x[1] as f32, self.y[1] as f32));
+                self.path.line_to(self.x[1], self.y[1]);
             } else if n > 1 {
                 let px = CurveNaturalContext::control_points(self.x.as_slice());
                 let py = CurveNaturalContext::control_points(self.y.as_slice());
 
                 for (i1, i0) in (1..n).zip(0..) {
-                    let ctrl0 = Vector2F::new(px.0[i0] as f32, py.0[i0] as f32);
-                    let ctrl1 = Vector2F::new(px.1[i0] as f32, py.1[i0] as f32);
-                    let to = Vector2F::new(self.x[i1] as f32, self.y[i1] as f32);
-                    self.path.bezier_curve_to(ctrl0, ctrl1, to);
+                    self.path.bezier_curve_to(
+                        px.0[i0], py.0[i0], px.1[i0], py.1[i0], self.x[i1], self.y[i1],
+                    );
                 }
             }
         }
-        if n == 1 {
+        if self.area_line == Some(true) || (self.area_line.is_none() && n == 1) {
             self.path.close_path();
         }
+        self.area_line = self.area_line.map(|line| !line);
         self.x = vec![];
         self.y = vec![];
     }
-
-    fn path(&self) -> &Path2D {
-        &self.path
-    }
 }
 
 #[cfg(test)]
@@ -137,6 +142,7 @@ mod tests {
         use crate::shape::line::Line;
 
         let mut line = Line::<(f64, f64)>::new()
+            .digits(Some(6))
             .x(Box::new(|datum, _i| datum.0))
             .y(Box::new(|datum, _i| datum.1))
             .curve(Box::new(CurveNatural::new()));
@@ -144,21 +150,20 @@ mod tests {
         {
             // test.pathEqual(l([[0, 1]]), "M0,1Z");
             let data: &[(f64, f64)] = &[(0., 1.)];
-            assert_eq!("M 0 1 L 0 1 z", line.generate(data));
+            assert_eq!("M0,1Z", line.generate(data));
         }
 
         {
             // test.pathEqual(l([[0, 1], [1, 3]]), "M0,1L1,3");
             let data: &[(f64, f64)] = &[(0., 1.), (1., 3.)];
-            assert_eq!("M 0 1 L 1 3", line.generate(data));
+            assert_eq!("M0,1L1,3", line.generate(data));
         }
 
-        // NOTE: Rust string formatting rounds with more precision than Javascript by default
         {
             // test.pathEqual(l([[0, 1], [1, 3], [2, 1]]), "M0,1C0.333333,2,0.666667,3,1,3C1.333333,3,1.666667,2,2,1");
             let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.)];
             assert_eq!(
-                "M 0 1 C 0.33333334 2 0.6666667 3 1 3 C 1.3333334 3 1.6666666 2 2 1",
+                "M0,1C0.333333,2,0.666667,3,1,3C1.333333,3,1.666667,2,2,1",
                 line.generate(data)
             );
         }
@@ -166,7 +171,7 @@ mod tests {
         {
             // test.pathEqual(l([[0, 1], [1, 3], [2, 1], [3, 3]]), "M0,1C0.333333,2.111111,0.666667,3.222222,1,3C1.333333,2.777778,1.666667,1.222222,2,1C2.333333,0.777778,2.666667,1.888889,3,3");
             let data: &[(f64, f64)] = &[(0., 1.), (1., 3.), (2., 1.), (3., 3.)];
-            assert_eq!("M 0 1 C 0.33333334 2.1111112 0.6666667 3.2222223 1 3 C 1.3333334 2.7777777 1.6666666 1.2222222 2 1 C 2.3333333 0.7777778 2.6666667 1.8888888 3 3", line.generate(data));
+            assert_eq!("M0,1C0.333333,2.111111,0.666667,3.222222,1,3C1.333333,2.777778,1.666667,1.222222,2,1C2.333333,0.777778,2.666667,1.888889,3,3", line.generate(data));
         }
     }
 }
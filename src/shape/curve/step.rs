@@ -3,11 +3,12 @@
 //! `CurveStep` creates a piecewise constant function (a.k.a. a step function)
 //! consisting of alternating horizontal and vertical lines. The y-value
 //! changes at the midpoint of each pair of adjacent x-values.
-
-use pathfinder_canvas::Path2D;
-use pathfinder_geometry::vector::Vector2F;
+//!
+//! `CurveStepBefore` changes the y-value before the x-value and
+//! `CurveStepAfter` changes the y-value after the x-value.
 
 use super::{CurveContext, CurveGenerator};
+use crate::path::PathContext;
 
 #[derive(Debug)]
 pub struct CurveStep {}
@@ -19,14 +20,27 @@ impl CurveStep {
 }
 
 impl CurveGenerator for CurveStep {
-    fn context(&self) -> Box<dyn CurveContext> {
-        Box::new(CurveStepContext {
-            line_state: 0,
-            x: None,
-            y: None,
-            t: 0.5,
-            path: Path2D::new(),
-        })
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveStepContext::new(path, 0.5))
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+#[derive(Debug)]
+pub struct CurveStepBefore {}
+
+impl CurveStepBefore {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CurveGenerator for CurveStepBefore {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveStepContext::new(path, 0.0))
     }
 
     fn size_hint(&self, points_count: usize) -> usize {
@@ -35,15 +49,57 @@ impl CurveGenerator for CurveStep {
 }
 
 #[derive(Debug)]
-pub struct CurveStepContext {
+pub struct CurveStepAfter {}
+
+impl CurveStepAfter {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl CurveGenerator for CurveStepAfter {
+    fn context<'a>(&self, path: &'a mut dyn PathContext) -> Box<dyn CurveContext + 'a> {
+        Box::new(CurveStepContext::new(path, 1.0))
+    }
+
+    fn size_hint(&self, points_count: usize) -> usize {
+        points_count
+    }
+}
+
+pub struct CurveStepContext<'a> {
     line_state: u8,
+    area_line: Option<bool>,
     x: Option<f64>,
     y: Option<f64>,
     t: f64,
-    path: Path2D,
+    path: &'a mut dyn PathContext,
 }
 
-impl CurveContext for CurveStepContext {
+impl<'a> CurveStepContext<'a> {
+    /// Constructs a new step context where the y-value changes at `t`, the
+    /// fraction of the way between each pair of adjacent x-values.
+    fn new(path: &'a mut dyn PathContext, t: f64) -> Self {
+        Self {
+            line_state: 0,
+            area_line: None,
+            x: None,
+            y: None,
+            t,
+            path,
+        }
+    }
+}
+
+impl<'a> CurveContext for CurveStepContext<'a> {
+    fn start_area(&mut self) {
+        self.area_line = Some(false);
+    }
+
+    fn end_area(&mut self) {
+        self.area_line = None;
+    }
+
     fn start_line(&mut self) {
         self.line_state = 0;
         self.x = None;
@@ -54,19 +110,26 @@ impl CurveContext for CurveStepContext {
         match self.line_state {
             0 => {
                 self.line_state += 1;
-                self.path.move_to(Vector2F::new(x as f32, y as f32));
+                if self.area_line == Some(true) {
+                    self.path.line_to(x, y);
+                } else {
+                    self.path.move_to(x, y);
+                }
             }
             _ => {
                 if self.line_state == 1 {
                     self.line_state += 1;
                 }
 
-                let x1: f32 = (self.x.unwrap() * (1.0 - self.t) + x * self.t) as f32;
-                let y_previous: f32 = self.y.unwrap() as f32;
-                let y: f32 = y as f32;
+                if self.t <= 0.0 {
+                    self.path.line_to(self.x.unwrap(), y);
+                    self.path.line_to(x, y);
+                } else {
+                    let x1 = self.x.unwrap() * (1.0 - self.t) + x * self.t;
 
-                self.path.line_to(Vector2F::new(x1, y_previous));
-                self.path.line_to(Vector2F::new(x1, y));
+                    self.path.line_to(x1, self.y.unwrap());
+                    self.path.line_to(x1, y);
+                }
             }
         }
 
@@ -76,19 +139,18 @@ impl CurveContext for CurveStepContext {
 
     fn end_line(&mut self) {
         if 0.0 < self.t && self.t < 1.0 && self.line_state == 2 {
-            self.path.line_to(Vector2F::new(
-                self.x.unwrap() as f32,
-                self.y.unwrap() as f32,
-            ));
+            self.path.line_to(self.x.unwrap(), self.y.unwrap());
         }
 
-        if self.line_state == 1 {
+        if self.area_line == Some(true) || (self.area_line.is_none() && self.line_state == 1) {
             self.path.close_path();
         }
-    }
 
-    fn path(&self) -> &Path2D {
-        &self.path
+        // The baseline of an area is drawn in reverse, so the step is mirrored.
+        if let Some(line) = self.area_line {
+            self.t = 1.0 - self.t;
+            self.area_line = Some(!line);
+        }
     }
 }
 
@@ -107,17 +169,90 @@ mod tests {
 
         {
             let data: &[(f64, f64)] = &[(0., 1.)];
-            assert_eq!("M 0 1 L 0 1 z", line.generate(data));
+            assert_eq!("M0,1Z", line.generate(data));
         }
 
         {
             let data: &[(f64, f64)] = &[(0., 1.), (2., 3.)];
-            assert_eq!("M 0 1 L 1 1 L 1 3 L 2 3", line.generate(data));
+            assert_eq!("M0,1L1,1L1,3L2,3", line.generate(data));
         }
 
         {
             let data: &[(f64, f64)] = &[(0., 1.), (2., 3.), (4., 5.)];
-            assert_eq!("M 0 1 L 1 1 L 1 3 L 3 3 L 3 5 L 4 5", line.generate(data));
+            assert_eq!("M0,1L1,1L1,3L3,3L3,5L4,5", line.generate(data));
         }
     }
+
+    #[test]
+    fn step_before_expected_results() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveStepBefore::new()));
+
+        {
+            // test.pathEqual(l([[0, 1]]), "M0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.)];
+            assert_eq!("M0,1Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [2, 3]]), "M0,1L0,3L2,3");
+            let data: &[(f64, f64)] = &[(0., 1.), (2., 3.)];
+            assert_eq!("M0,1L0,3L2,3", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [2, 3], [4, 5]]), "M0,1L0,3L2,3L2,5L4,5");
+            let data: &[(f64, f64)] = &[(0., 1.), (2., 3.), (4., 5.)];
+            assert_eq!("M0,1L0,3L2,3L2,5L4,5", line.generate(data));
+        }
+    }
+
+    #[test]
+    fn step_after_expected_results() {
+        use crate::shape::line::Line;
+
+        let mut line = Line::<(f64, f64)>::new()
+            .x(Box::new(|datum, _i| datum.0))
+            .y(Box::new(|datum, _i| datum.1))
+            .curve(Box::new(CurveStepAfter::new()));
+
+        {
+            // test.pathEqual(l([[0, 1]]), "M0,1Z");
+            let data: &[(f64, f64)] = &[(0., 1.)];
+            assert_eq!("M0,1Z", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [2, 3]]), "M0,1L2,1L2,3");
+            let data: &[(f64, f64)] = &[(0., 1.), (2., 3.)];
+            assert_eq!("M0,1L2,1L2,3", line.generate(data));
+        }
+
+        {
+            // test.pathEqual(l([[0, 1], [2, 3], [4, 5]]), "M0,1L2,1L2,3L4,3L4,5");
+            let data: &[(f64, f64)] = &[(0., 1.), (2., 3.), (4., 5.)];
+            assert_eq!("M0,1L2,1L2,3L4,3L4,5", line.generate(data));
+        }
+    }
+
+    #[test]
+    fn step_after_area_mirrors_the_baseline() {
+        use crate::shape::area::Area;
+
+        let mut area = Area::<(f64, f64)>::new()
+            .x(Box::new(|datum, _i| datum.0))
+            .y1(Some(Box::new(|datum, _i| datum.1)))
+            .curve(Box::new(CurveStepAfter::new()));
+
+        // The baseline is drawn in reverse, so it steps before each x-value.
+        let data: &[(f64, f64)] = &[(0., 1.), (2., 3.), (4., 5.)];
+        assert_eq!(
+            "M0,1L2,1L2,3L4,3L4,5L4,0L4,0L2,0L2,0L0,0Z",
+            area.generate(data)
+        );
+    }
 }
@@ -2,6 +2,7 @@
 
 use std::marker::PhantomData;
 
+use crate::path::{PathContext, SvgPath};
 use crate::shape::curve::{CurveGenerator, CurveLinear};
 
 /// `Line` is a Cartesian line generator.
@@ -10,6 +11,7 @@ pub struct Line<'a, SourceType> {
     x: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>,
     y: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>,
     defined: Box<dyn FnMut(&SourceType, usize) -> bool + 'a>,
+    digits: Option<usize>,
     phantom: PhantomData<SourceType>,
 }
 
@@ -21,6 +23,7 @@ impl<'a, SourceType> Line<'a, SourceType> {
             x: Box::new(|_, _| unimplemented!("X accessor not implemented")),
             y: Box::new(|_, _| unimplemented!("Y accessor not implemented")),
             defined: Box::new(|_, _| true),
+            digits: Some(3),
             phantom: PhantomData {},
         }
     }
@@ -70,8 +73,25 @@ impl<'a, SourceType> Line<'a, SourceType> {
         Self { defined, ..self }
     }
 
+    /// Sets the number of fractional digits written by
+    /// [generate](Line::generate).  If `None` coordinates are written at full
+    /// precision.  Defaults to three digits.
+    pub fn digits(self, digits: Option<usize>) -> Self {
+        Self { digits, ..self }
+    }
+
+    /// Generates a line for the given data and returns it as SVG path data.
     pub fn generate(&mut self, data: &[SourceType]) -> String {
-        let mut context = self.curve.context();
+        let mut path = SvgPath::new().digits(self.digits);
+        self.generate_to(data, &mut path);
+
+        path.to_string()
+    }
+
+    /// Generates a line for the given data, drawing it into the given path
+    /// context.
+    pub fn generate_to(&mut self, data: &[SourceType], path: &mut dyn PathContext) {
+        let mut context = self.curve.context(path);
 
         let mut started = false;
 
@@ -86,9 +106,6 @@ impl<'a, SourceType> Line<'a, SourceType> {
             }
         }
         context.end_line();
-        let path_string = context.path().clone().into_outline();
-
-        format!("{:#?}", path_string)
     }
 }
 
@@ -101,17 +118,5 @@ pub trait LineSource {
 // pub struct CurveBasis {}
 // impl Curve for CurveBasis {}
 
-// pub struct CurveBundle {}
-// impl Curve for CurveBundle {}
-
 // pub struct CurveCardinal {}
 // impl Curve for CurveCardinal {}
-
-// pub struct CurveCatmullRom {}
-// impl Curve for CurveCatmullRom {}
-
-// pub struct CurveMonotoneX {}
-// impl Curve for CurveMonotoneX {}
-
-// pub struct CurveMonotoneY {}
-// impl Curve for CurveMonotoneY {}
@@ -1,4 +1,8 @@
+pub mod arc;
+pub mod area;
 pub mod curve;
 pub mod line;
 pub mod offset;
+pub mod order;
+pub mod pie;
 pub mod stack;
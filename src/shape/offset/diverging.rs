@@ -0,0 +1,74 @@
+//! Positive values are stacked above zero, negative values are stacked below
+//! zero, and zero values are stacked at zero.
+
+use crate::shape::offset::{values, OffsetGenerator};
+use crate::shape::stack::{StackIntermediateRow, StackRow};
+
+#[derive(Debug)]
+pub struct OffsetDiverging {}
+
+impl OffsetGenerator for OffsetDiverging {
+    fn offset(&self, intermediate: StackIntermediateRow) -> Vec<StackRow> {
+        let values = values(&intermediate);
+        let row_len = intermediate[0].len();
+
+        let mut positive = vec![0.0; row_len];
+        let mut negative = vec![0.0; row_len];
+
+        values
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .enumerate()
+                    .map(|(j, value)| {
+                        if *value > 0.0 {
+                            let start = positive[j];
+                            positive[j] += value;
+                            start..positive[j]
+                        } else if *value < 0.0 {
+                            let end = negative[j];
+                            negative[j] += value;
+                            negative[j]..end
+                        } else {
+                            0.0..*value
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacks_positive_and_negative_values_separately() {
+        let series: StackIntermediateRow = vec![
+            vec![1.0, 2.0, -1.0],
+            vec![4.0, 6.0, -3.0],
+            vec![9.0, 4.0, -7.0],
+        ];
+
+        let stacked = (OffsetDiverging {}).offset(series);
+
+        assert_eq!(vec![0.0..1.0, 0.0..2.0, -1.0..0.0], stacked[0]);
+        assert_eq!(vec![1.0..4.0, 2.0..6.0, -3.0..-1.0], stacked[1]);
+        assert_eq!(vec![4.0..9.0, -2.0..0.0, -7.0..-3.0], stacked[2]);
+    }
+
+    #[test]
+    fn treats_nan_as_zero() {
+        let series: StackIntermediateRow =
+            vec![vec![1.0, 2.0], vec![4.0, std::f64::NAN], vec![9.0, 4.0]];
+
+        let stacked = (OffsetDiverging {}).offset(series);
+
+        assert_eq!(vec![0.0..1.0, 0.0..2.0], stacked[0]);
+        assert_eq!(1.0..4.0, stacked[1][0]);
+        assert_eq!(0.0, stacked[1][1].start);
+        assert_eq!(true, stacked[1][1].end.is_nan());
+        assert_eq!(vec![4.0..9.0, 2.0..4.0], stacked[2]);
+    }
+}
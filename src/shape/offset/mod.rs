@@ -7,8 +7,62 @@ pub trait OffsetGenerator {
     fn offset(&self, intermediate: StackIntermediateRow) -> Vec<StackRow>;
 }
 
+/// Recovers the value of each series from the cumulative sums in
+/// `intermediate`.  A NaN sum is treated as a zero value when computing the
+/// series above it, but remains NaN itself.
+fn values(intermediate: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let row_len = intermediate.first().map_or(0, |row| row.len());
+    let mut below = vec![0.0; row_len];
+
+    intermediate
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(below.iter_mut())
+                .map(|(cell, below)| {
+                    if cell.is_nan() {
+                        std::f64::NAN
+                    } else {
+                        let value = cell - *below;
+                        *below = *cell;
+                        value
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Stacks the given series values on top of the given baseline.  A NaN value
+/// produces a range ending in NaN and doesn't move the series above it.
+fn stack_on(values: &[Vec<f64>], baseline: &[f64]) -> Vec<StackRow> {
+    let mut top = baseline.to_vec();
+
+    values
+        .iter()
+        .map(|row| {
+            row.iter()
+                .zip(top.iter_mut())
+                .map(|(value, top)| {
+                    let start = *top;
+                    if !value.is_nan() {
+                        *top += value;
+                    }
+                    start..(start + value)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+mod diverging;
 mod expand;
 mod none;
+mod silhouette;
+mod wiggle;
 
+pub use diverging::*;
 pub use expand::*;
 pub use none::*;
+pub use silhouette::*;
+pub use wiggle::*;
@@ -9,12 +9,13 @@ impl OffsetGenerator for OffsetNone {
     fn offset(&self, intermediate: StackIntermediateRow) -> Vec<StackRow> {
         let row_len = intermediate[0].len();
 
-        let mut ret = vec![vec![0.0..0.0; row_len]; intermediate.len()];
+        let mut ret = vec![vec![0.0_f64..0.0; row_len]; intermediate.len()];
 
         for (i, row) in intermediate.iter().enumerate() {
             for (j, cell) in row.iter().enumerate() {
                 let start = match i {
                     0 => 0.,
+                    _ if ret[i - 1][j].end.is_nan() => ret[i - 1][j].start,
                     _ => ret[i - 1][j].end,
                 };
 
@@ -0,0 +1,62 @@
+//! Shifts the baseline down such that the center of the streamgraph is always
+//! at zero.
+
+use crate::shape::offset::{stack_on, values, OffsetGenerator};
+use crate::shape::stack::{StackIntermediateRow, StackRow};
+
+#[derive(Debug)]
+pub struct OffsetSilhouette {}
+
+impl OffsetGenerator for OffsetSilhouette {
+    fn offset(&self, intermediate: StackIntermediateRow) -> Vec<StackRow> {
+        let values = values(&intermediate);
+        let row_len = intermediate[0].len();
+
+        let baseline: Vec<f64> = (0..row_len)
+            .map(|j| {
+                let sum: f64 = values
+                    .iter()
+                    .map(|row| row[j])
+                    .filter(|value| !value.is_nan())
+                    .sum();
+                -sum / 2.0
+            })
+            .collect();
+
+        stack_on(&values, &baseline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn centers_the_stack_at_zero() {
+        let series: StackIntermediateRow = vec![
+            vec![1.0, 2.0, 1.0],
+            vec![4.0, 6.0, 3.0],
+            vec![9.0, 8.0, 7.0],
+        ];
+
+        let stacked = (OffsetSilhouette {}).offset(series);
+
+        assert_eq!(vec![-4.5..-3.5, -4.0..-2.0, -3.5..-2.5], stacked[0]);
+        assert_eq!(vec![-3.5..-0.5, -2.0..2.0, -2.5..-0.5], stacked[1]);
+        assert_eq!(vec![-0.5..4.5, 2.0..4.0, -0.5..3.5], stacked[2]);
+    }
+
+    #[test]
+    fn treats_nan_as_zero() {
+        let series: StackIntermediateRow =
+            vec![vec![1.0, 2.0], vec![4.0, std::f64::NAN], vec![9.0, 4.0]];
+
+        let stacked = (OffsetSilhouette {}).offset(series);
+
+        assert_eq!(vec![-4.5..-3.5, -2.0..0.0], stacked[0]);
+        assert_eq!(-3.5..-0.5, stacked[1][0]);
+        assert_eq!(0.0, stacked[1][1].start);
+        assert_eq!(true, stacked[1][1].end.is_nan());
+        assert_eq!(vec![-0.5..4.5, 0.0..2.0], stacked[2]);
+    }
+}
@@ -0,0 +1,101 @@
+//! Shifts the baseline so as to minimize the weighted wiggle of layers.
+//!
+//! This offset is recommended for streamgraphs in conjunction with
+//! [OrderInsideOut](crate::shape::order::OrderInsideOut).  See [Stacked
+//! Graphs—Geometry & Aesthetics](http://leebyron.com/streamgraph/) by Byron
+//! & Wattenberg for more information.
+
+use crate::shape::offset::{stack_on, values, OffsetGenerator};
+use crate::shape::stack::{StackIntermediateRow, StackRow};
+
+#[derive(Debug)]
+pub struct OffsetWiggle {}
+
+impl OffsetGenerator for OffsetWiggle {
+    fn offset(&self, intermediate: StackIntermediateRow) -> Vec<StackRow> {
+        let values = values(&intermediate);
+        let row_len = intermediate[0].len();
+        let value = |i: usize, j: usize| {
+            let value: f64 = values[i][j];
+            if value.is_nan() {
+                0.0
+            } else {
+                value
+            }
+        };
+
+        let mut baseline = vec![0.0; row_len];
+        let mut y = 0.0;
+
+        for (j, baseline) in baseline.iter_mut().enumerate().skip(1) {
+            let mut s1 = 0.0;
+            let mut s2 = 0.0;
+
+            for i in 0..values.len() {
+                let sij0 = value(i, j);
+                let sij1 = value(i, j - 1);
+                let mut s3 = (sij0 - sij1) / 2.0;
+
+                for k in 0..i {
+                    s3 += value(k, j) - value(k, j - 1);
+                }
+
+                s1 += sij0;
+                s2 += s3 * sij0;
+            }
+
+            if s1 != 0.0 {
+                y -= s2 / s1;
+            }
+            *baseline = y;
+        }
+
+        stack_on(&values, &baseline)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    #[test]
+    fn minimizes_weighted_wiggle() {
+        let series: StackIntermediateRow = vec![
+            vec![1.0, 2.0, 1.0],
+            vec![4.0, 6.0, 3.0],
+            vec![9.0, 8.0, 7.0],
+        ];
+
+        let stacked = (OffsetWiggle {}).offset(series);
+
+        let expected: &[&[(f64, f64)]] = &[
+            &[(0.0, 1.0), (-1.0, 1.0), (0.7857143, 1.7857143)],
+            &[(1.0, 4.0), (1.0, 5.0), (1.7857143, 3.7857143)],
+            &[(4.0, 9.0), (5.0, 7.0), (3.7857143, 7.7857143)],
+        ];
+
+        for (expected, actual) in expected.iter().zip(stacked.iter()) {
+            for (expected, actual) in expected.iter().zip(actual.iter()) {
+                assert_delta!(expected.0, actual.start, DELTA);
+                assert_delta!(expected.1, actual.end, DELTA);
+            }
+        }
+    }
+
+    #[test]
+    fn treats_nan_as_zero() {
+        let series: StackIntermediateRow =
+            vec![vec![1.0, 2.0], vec![4.0, std::f64::NAN], vec![9.0, 4.0]];
+
+        let stacked = (OffsetWiggle {}).offset(series);
+
+        // The missing value is weighted as a drop from 3 to zero.
+        assert_eq!(vec![0.0..1.0, 1.5..3.5], stacked[0]);
+        assert_eq!(1.0..4.0, stacked[1][0]);
+        assert_eq!(3.5, stacked[1][1].start);
+        assert_eq!(true, stacked[1][1].end.is_nan());
+        assert_eq!(vec![4.0..9.0, 3.5..5.5], stacked[2]);
+    }
+}
@@ -0,0 +1,48 @@
+//! Returns a series order such that the earliest series (according to the
+//! maximum value) is at the bottom.
+
+use crate::shape::order::{OrderGenerator, OrderNone};
+
+#[derive(Debug)]
+pub struct OrderAppearance {}
+
+/// Returns the index of the first maximum value of a series, ignoring NaN.
+pub(crate) fn peak(series: &[f64]) -> usize {
+    let mut peak = 0;
+    let mut max = std::f64::NEG_INFINITY;
+
+    for (i, value) in series.iter().enumerate() {
+        if *value > max {
+            max = *value;
+            peak = i;
+        }
+    }
+
+    peak
+}
+
+impl OrderGenerator for OrderAppearance {
+    fn order(&self, series: &[Vec<f64>]) -> Vec<usize> {
+        let peaks: Vec<usize> = series.iter().map(|values| peak(values)).collect();
+        let mut order = (OrderNone {}).order(series);
+
+        order.sort_by_key(|i| peaks[*i]);
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_by_peak_index() {
+        let series = vec![
+            vec![0.0, 1.0, 4.0],
+            vec![1.0, 2.0, 3.0],
+            vec![0.0, 4.0, 1.0],
+        ];
+
+        assert_eq!(vec![2, 0, 1], (OrderAppearance {}).order(&series));
+    }
+}
@@ -0,0 +1,47 @@
+//! Returns a series order such that the smallest series (according to the sum
+//! of values) is at the bottom.
+
+use std::cmp::Ordering;
+
+use crate::shape::order::{OrderGenerator, OrderNone};
+
+#[derive(Debug)]
+pub struct OrderAscending {}
+
+/// Sums the values of a series, ignoring NaN.
+pub(crate) fn sum(series: &[f64]) -> f64 {
+    series.iter().filter(|value| !value.is_nan()).sum()
+}
+
+impl OrderGenerator for OrderAscending {
+    fn order(&self, series: &[Vec<f64>]) -> Vec<usize> {
+        let sums: Vec<f64> = series.iter().map(|values| sum(values)).collect();
+        let mut order = (OrderNone {}).order(series);
+
+        order.sort_by(|a, b| sums[*a].partial_cmp(&sums[*b]).unwrap_or(Ordering::Equal));
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_by_sum_of_values() {
+        let series = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![2.0, 3.0, 4.0],
+            vec![0.0, 1.0, 2.0],
+        ];
+
+        assert_eq!(vec![2, 0, 1], (OrderAscending {}).order(&series));
+    }
+
+    #[test]
+    fn ignores_nan() {
+        let series = vec![vec![4.0, std::f64::NAN], vec![1.0, 2.0]];
+
+        assert_eq!(vec![1, 0], (OrderAscending {}).order(&series));
+    }
+}
@@ -0,0 +1,31 @@
+//! Returns a series order such that the largest series (according to the sum
+//! of values) is at the bottom.
+
+use crate::shape::order::{OrderAscending, OrderGenerator};
+
+#[derive(Debug)]
+pub struct OrderDescending {}
+
+impl OrderGenerator for OrderDescending {
+    fn order(&self, series: &[Vec<f64>]) -> Vec<usize> {
+        let mut order = (OrderAscending {}).order(series);
+        order.reverse();
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_by_sum_of_values() {
+        let series = vec![
+            vec![1.0, 2.0, 3.0],
+            vec![2.0, 3.0, 4.0],
+            vec![0.0, 1.0, 2.0],
+        ];
+
+        assert_eq!(vec![1, 0, 2], (OrderDescending {}).order(&series));
+    }
+}
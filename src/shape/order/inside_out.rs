@@ -0,0 +1,56 @@
+//! Returns a series order such that the earliest series (according to the
+//! maximum value) are on the inside and the later series are on the outside.
+//!
+//! This order is recommended for streamgraphs in conjunction with
+//! [OffsetWiggle](crate::shape::offset::OffsetWiggle).
+
+use crate::shape::order::{sum, OrderAppearance, OrderGenerator};
+
+#[derive(Debug)]
+pub struct OrderInsideOut {}
+
+impl OrderGenerator for OrderInsideOut {
+    fn order(&self, series: &[Vec<f64>]) -> Vec<usize> {
+        let sums: Vec<f64> = series.iter().map(|values| sum(values)).collect();
+
+        let mut top = 0.0;
+        let mut bottom = 0.0;
+        let mut tops = vec![];
+        let mut bottoms = vec![];
+
+        for j in (OrderAppearance {}).order(series) {
+            if top < bottom {
+                top += sums[j];
+                tops.push(j);
+            } else {
+                bottom += sums[j];
+                bottoms.push(j);
+            }
+        }
+
+        bottoms.reverse();
+        bottoms.extend(tops);
+        bottoms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn puts_the_earliest_series_on_the_inside() {
+        // By appearance the series are [3, 2, 0, 1, 4, 5]; each is added to
+        // whichever of the top or bottom has the smaller sum so far.
+        let series = vec![
+            vec![0.0, 1.0, 2.0],
+            vec![0.0, 0.0, 1.0],
+            vec![0.0, 2.0, 0.0],
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 3.0],
+            vec![0.0, 0.0, 4.0],
+        ];
+
+        assert_eq!(vec![5, 0, 3, 2, 1, 4], (OrderInsideOut {}).order(&series));
+    }
+}
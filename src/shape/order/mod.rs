@@ -0,0 +1,26 @@
+//! Order functions return the order in which to stack an array of series.
+
+/// Implement this trait to provide a new order generator.
+///
+/// The series are given in the order returned by
+/// [Stackable::values](crate::shape::stack::Stackable::values), each
+/// containing the raw value for every element of the input data.  The
+/// returned vector contains the series indices in stacking order, from the
+/// baseline up.
+pub trait OrderGenerator {
+    fn order(&self, series: &[Vec<f64>]) -> Vec<usize>;
+}
+
+mod appearance;
+mod ascending;
+mod descending;
+mod inside_out;
+mod none;
+mod reverse;
+
+pub use appearance::*;
+pub use ascending::*;
+pub use descending::*;
+pub use inside_out::*;
+pub use none::*;
+pub use reverse::*;
@@ -0,0 +1,26 @@
+//! Returns the given series order [0, 1, … n - 1] where n is the number of
+//! series.
+
+use crate::shape::order::OrderGenerator;
+
+#[derive(Debug)]
+pub struct OrderNone {}
+
+impl OrderGenerator for OrderNone {
+    fn order(&self, series: &[Vec<f64>]) -> Vec<usize> {
+        (0..series.len()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_the_given_order() {
+        // test.deepEqual(stackOrderNone([1, 2, 3]), [0, 1, 2]);
+        let series = vec![vec![1.0], vec![2.0], vec![3.0]];
+
+        assert_eq!(vec![0, 1, 2], (OrderNone {}).order(&series));
+    }
+}
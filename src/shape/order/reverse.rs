@@ -0,0 +1,28 @@
+//! Returns the reverse of the given series order [n - 1, n - 2, … 0] where n
+//! is the number of series.
+
+use crate::shape::order::{OrderGenerator, OrderNone};
+
+#[derive(Debug)]
+pub struct OrderReverse {}
+
+impl OrderGenerator for OrderReverse {
+    fn order(&self, series: &[Vec<f64>]) -> Vec<usize> {
+        let mut order = (OrderNone {}).order(series);
+        order.reverse();
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn returns_the_reverse_order() {
+        // test.deepEqual(stackOrderReverse([1, 2, 3]), [2, 1, 0]);
+        let series = vec![vec![1.0], vec![2.0], vec![3.0]];
+
+        assert_eq!(vec![2, 1, 0], (OrderReverse {}).order(&series));
+    }
+}
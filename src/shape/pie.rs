@@ -0,0 +1,289 @@
+//! The pie generator computes the angles necessary to represent a tabular
+//! dataset as a pie or donut chart; these angles can then be passed to an
+//! [arc](crate::shape::arc::Arc) generator.
+
+use std::cmp::Ordering;
+use std::f64::consts::PI;
+
+const TAU: f64 = 2.0 * PI;
+
+/// A single slice of a pie, as computed by [Pie](Pie).
+#[derive(Debug, PartialEq)]
+pub struct PieArc<'a, SourceType> {
+    /// The input datum.
+    pub data: &'a SourceType,
+    /// The numeric value of the arc.
+    pub value: f64,
+    /// The zero-based sorted index of the arc.
+    pub index: usize,
+    /// The start angle of the arc.
+    pub start_angle: f64,
+    /// The end angle of the arc.
+    pub end_angle: f64,
+    /// The pad angle of the arc.
+    pub pad_angle: f64,
+}
+
+/// `Pie` is a pie layout generator.
+pub struct Pie<'a, SourceType> {
+    value: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>,
+    sort: Option<Box<dyn FnMut(&SourceType, &SourceType) -> Ordering + 'a>>,
+    sort_values: Option<Box<dyn FnMut(f64, f64) -> Ordering + 'a>>,
+    start_angle: f64,
+    end_angle: f64,
+    pad_angle: f64,
+}
+
+impl<'a, SourceType> Pie<'a, SourceType> {
+    /// Constructs a new pie generator.  Arcs are sorted by descending value,
+    /// start at 0 and end at τ, with no padding.
+    pub fn new() -> Self {
+        Self {
+            value: Box::new(|_, _| unimplemented!("Value accessor not implemented")),
+            sort: None,
+            sort_values: Some(Box::new(|a, b| {
+                b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+            })),
+            start_angle: 0.0,
+            end_angle: TAU,
+            pad_angle: 0.0,
+        }
+    }
+
+    /// Sets the `value` accessor to the specified closure and returns the pie
+    /// generator.
+    ///
+    /// The `value` accessor is invoked for each element in the input data
+    /// collection.  The accessor takes two parameters: the element (of type
+    /// SourceType) and the index.  The default accessor calls the
+    /// `unimplemented!` macro resulting in a panic.
+    pub fn value(self, value: Box<dyn FnMut(&SourceType, usize) -> f64 + 'a>) -> Self {
+        Self { value, ..self }
+    }
+
+    /// Sets the data comparator and clears the value comparator.  The
+    /// comparator orders the arcs but does not affect the order of the
+    /// returned slices.  If `None` the arcs are laid out in input order.
+    pub fn sort(
+        self,
+        sort: Option<Box<dyn FnMut(&SourceType, &SourceType) -> Ordering + 'a>>,
+    ) -> Self {
+        Self {
+            sort,
+            sort_values: None,
+            ..self
+        }
+    }
+
+    /// Sets the value comparator and clears the data comparator.  The
+    /// comparator orders the arcs but does not affect the order of the
+    /// returned slices.  If `None` the arcs are laid out in input order.
+    pub fn sort_values(
+        self,
+        sort_values: Option<Box<dyn FnMut(f64, f64) -> Ordering + 'a>>,
+    ) -> Self {
+        Self {
+            sort: None,
+            sort_values,
+            ..self
+        }
+    }
+
+    /// Sets the overall start angle of the pie, in radians.  An angle of 0 is
+    /// at 12 o’clock and positive angles proceed clockwise.
+    pub fn start_angle(self, start_angle: f64) -> Self {
+        Self {
+            start_angle,
+            ..self
+        }
+    }
+
+    /// Sets the overall end angle of the pie, in radians.  If the absolute
+    /// difference between the start and end angles is greater than τ, it is
+    /// clamped to τ.
+    pub fn end_angle(self, end_angle: f64) -> Self {
+        Self { end_angle, ..self }
+    }
+
+    /// Sets the pad angle, the angular separation between adjacent arcs.  The
+    /// total amount of padding is the pad angle times the number of elements
+    /// and is reserved before the remaining space is distributed.
+    pub fn pad_angle(self, pad_angle: f64) -> Self {
+        Self { pad_angle, ..self }
+    }
+
+    /// Generates a pie for the given data.  The returned slices are in the
+    /// same order as the input data, regardless of the sort order.  Negative
+    /// and NaN values are treated as zero.
+    pub fn generate<'b>(&mut self, data: &'b [SourceType]) -> Vec<PieArc<'b, SourceType>> {
+        let n = data.len();
+
+        let values: Vec<f64> = data
+            .iter()
+            .enumerate()
+            .map(|(i, datum)| (self.value)(datum, i))
+            .collect();
+
+        let sum: f64 = values.iter().filter(|value| **value > 0.0).sum();
+
+        let mut index: Vec<usize> = (0..n).collect();
+        if let Some(sort_values) = &mut self.sort_values {
+            index.sort_by(|i, j| sort_values(values[*i], values[*j]));
+        } else if let Some(sort) = &mut self.sort {
+            index.sort_by(|i, j| sort(&data[*i], &data[*j]));
+        }
+
+        let da = TAU.min((-TAU).max(self.end_angle - self.start_angle));
+        let p = (da.abs() / n as f64).min(self.pad_angle);
+        let pa = if da < 0.0 { -p } else { p };
+        let k = if sum != 0.0 {
+            (da - n as f64 * pa) / sum
+        } else {
+            0.0
+        };
+
+        let mut arcs: Vec<Option<PieArc<'b, SourceType>>> = (0..n).map(|_| None).collect();
+        let mut a0 = self.start_angle;
+
+        for (i, j) in index.into_iter().enumerate() {
+            let value = values[j];
+            let a1 = a0 + if value > 0.0 { value * k } else { 0.0 } + pa;
+
+            arcs[j] = Some(PieArc {
+                data: &data[j],
+                value,
+                index: i,
+                start_angle: a0,
+                end_angle: a1,
+                pad_angle: p,
+            });
+
+            a0 = a1;
+        }
+
+        arcs.into_iter()
+            .map(|arc| arc.expect("Every arc is assigned exactly once"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_delta, test::DELTA};
+
+    use super::*;
+
+    fn angles(arcs: &[PieArc<f64>]) -> Vec<(f64, usize, f64, f64)> {
+        arcs.iter()
+            .map(|arc| (arc.value, arc.index, arc.start_angle, arc.end_angle))
+            .collect()
+    }
+
+    #[test]
+    fn returns_arcs_in_input_order() {
+        let mut pie = Pie::<f64>::new().value(Box::new(|d, _i| *d));
+
+        let arcs = pie.generate(&[1.0, 3.0, 2.0]);
+        let expected = vec![
+            (1.0, 2, 5.235987755982988, 6.283185307179585),
+            (3.0, 0, 0.000000000000000, 3.141592653589793),
+            (2.0, 1, 3.141592653589793, 5.235987755982988),
+        ];
+
+        for (actual, expected) in angles(&arcs).into_iter().zip(expected) {
+            assert_eq!(expected.0, actual.0);
+            assert_eq!(expected.1, actual.1);
+            assert_delta!(expected.2, actual.2, DELTA);
+            assert_delta!(expected.3, actual.3, DELTA);
+        }
+        assert_eq!(&3.0, arcs[1].data);
+        assert_eq!(0.0, arcs[1].pad_angle);
+    }
+
+    #[test]
+    fn treats_negative_and_nan_values_as_zero() {
+        let mut pie = Pie::<f64>::new().value(Box::new(|d, _i| *d));
+
+        let arcs = pie.generate(&[1.0, 0.0, -1.0, std::f64::NAN]);
+
+        assert_delta!(0.0, arcs[0].start_angle, DELTA);
+        assert_delta!(TAU, arcs[0].end_angle, DELTA);
+        for arc in arcs[1..].iter() {
+            assert_delta!(TAU, arc.start_angle, DELTA);
+            assert_delta!(TAU, arc.end_angle, DELTA);
+        }
+    }
+
+    #[test]
+    fn puts_everything_at_the_start_angle_when_the_sum_is_zero() {
+        let mut pie = Pie::<f64>::new().value(Box::new(|d, _i| *d));
+
+        for arc in pie.generate(&[0.0, 0.0]).iter() {
+            assert_eq!(0.0, arc.start_angle);
+            assert_eq!(0.0, arc.end_angle);
+        }
+    }
+
+    #[test]
+    fn start_angle_offsets_the_pie() {
+        let mut pie = Pie::<f64>::new()
+            .value(Box::new(|d, _i| *d))
+            .start_angle(PI);
+
+        let arcs = pie.generate(&[1.0, 2.0]);
+
+        assert_delta!(5.235987755982989, arcs[0].start_angle, DELTA);
+        assert_delta!(6.283185307179586, arcs[0].end_angle, DELTA);
+        assert_delta!(3.141592653589793, arcs[1].start_angle, DELTA);
+        assert_delta!(5.235987755982989, arcs[1].end_angle, DELTA);
+    }
+
+    #[test]
+    fn pad_angle_is_reserved_between_arcs() {
+        let mut pie = Pie::<f64>::new().value(Box::new(|d, _i| *d)).pad_angle(0.1);
+
+        let arcs = pie.generate(&[1.0, 2.0, 3.0]);
+        let expected = vec![
+            (1.0, 2, 5.185987755982988, 6.283185307179586),
+            (2.0, 1, 3.091592653589793, 5.185987755982988),
+            (3.0, 0, 0.000000000000000, 3.091592653589793),
+        ];
+
+        for (actual, expected) in angles(&arcs).into_iter().zip(expected) {
+            assert_eq!(expected.1, actual.1);
+            assert_delta!(expected.2, actual.2, DELTA);
+            assert_delta!(expected.3, actual.3, DELTA);
+        }
+        assert_eq!(0.1, arcs[0].pad_angle);
+    }
+
+    #[test]
+    fn sort_none_preserves_input_order() {
+        let mut pie = Pie::<f64>::new()
+            .value(Box::new(|d, _i| *d))
+            .sort_values(None);
+
+        let arcs = pie.generate(&[1.0, 3.0, 2.0]);
+
+        assert_eq!(
+            vec![0, 1, 2],
+            arcs.iter().map(|arc| arc.index).collect::<Vec<_>>()
+        );
+        assert_delta!(PI / 3.0, arcs[0].end_angle, DELTA);
+    }
+
+    #[test]
+    fn sort_orders_by_data() {
+        let data = &["b", "c", "a"];
+        let mut pie = Pie::<&str>::new()
+            .value(Box::new(|_d, _i| 1.0))
+            .sort(Some(Box::new(|a, b| a.cmp(b))));
+
+        let arcs = pie.generate(data);
+
+        assert_eq!(
+            vec![1, 2, 0],
+            arcs.iter().map(|arc| arc.index).collect::<Vec<_>>()
+        );
+    }
+}
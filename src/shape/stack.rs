@@ -3,6 +3,7 @@
 use std::marker::PhantomData;
 
 use crate::shape::offset::{OffsetGenerator, OffsetNone};
+use crate::shape::order::{OrderGenerator, OrderNone};
 
 pub type StackIntermediateRow = Vec<Vec<f64>>;
 pub type StackRow = Vec<std::ops::Range<f64>>;
@@ -11,8 +12,22 @@ pub trait Stackable {
     fn values(&self) -> Vec<f64>;
 }
 
+/// A single stacked series, as computed by [Stack](Stack).
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackSeries {
+    /// The key of the series, if keys were given to the stack generator.
+    pub key: Option<String>,
+    /// The position of the series in the stacking order.
+    pub index: usize,
+    /// The lower and upper values of the series for each element of the
+    /// input data.
+    pub points: StackRow,
+}
+
 pub struct Stack<ItemType, IteratorType> {
     offset: Box<dyn OffsetGenerator>,
+    order: Box<dyn OrderGenerator>,
+    keys: Option<Vec<String>>,
     data: StackIntermediateRow,
 
     phantom_item: PhantomData<ItemType>,
@@ -29,6 +44,8 @@ where
     {
         Self {
             offset: Box::new(OffsetNone {}),
+            order: Box::new(OrderNone {}),
+            keys: None,
             data: it.map(|d| d.values()).collect(),
             phantom_item: PhantomData {},
             phantom_iterator: PhantomData {},
@@ -39,33 +56,74 @@ where
         Self { offset, ..self }
     }
 
-    pub fn stack(&self) -> Vec<StackRow>
+    /// Sets the order generator, which determines the order in which the
+    /// series are stacked.  The default order is [OrderNone](OrderNone).
+    pub fn order(self, order: Box<dyn OrderGenerator>) -> Self {
+        Self { order, ..self }
+    }
+
+    /// Sets the series keys.  The key at a given position names the series at
+    /// the same position in the values returned by
+    /// [Stackable::values](Stackable::values).
+    pub fn keys<KeyType: ToString>(self, keys: &[KeyType]) -> Self {
+        Self {
+            keys: Some(keys.iter().map(|key| key.to_string()).collect()),
+            ..self
+        }
+    }
+
+    /// Stacks the data.  The returned series are in the same order as the
+    /// values returned by [Stackable::values](Stackable::values), regardless
+    /// of the stacking order.
+    pub fn stack(&self) -> Vec<StackSeries>
     where
         IteratorType: std::iter::Iterator<Item = ItemType>,
     {
         let row_len = self.data[0].len();
 
+        let series: Vec<Vec<f64>> = (0..row_len)
+            .map(|j| self.data.iter().map(|row| row[j]).collect())
+            .collect();
+
+        let order = self.order.order(&series);
+
         let mut intermediate = vec![vec![0.0; self.data.len()]; row_len];
 
-        for (i, row) in self.data.iter().enumerate() {
-            for (j, cell) in row.iter().enumerate() {
-                let last = match j {
-                    0 => 0.,
-                    _ => intermediate[j - 1][i],
+        // A NaN value leaves a NaN sum for its own series, but counts as zero
+        // for the series stacked above it.
+        let mut sums = vec![0.0; self.data.len()];
+        for (k, j) in order.iter().enumerate() {
+            for (i, cell) in series[*j].iter().enumerate() {
+                intermediate[k][i] = if cell.is_nan() {
+                    std::f64::NAN
+                } else {
+                    sums[i] += cell;
+                    sums[i]
                 };
-
-                intermediate[j][i] = last + cell;
             }
         }
 
-        let ret = self.offset.offset(intermediate);
+        let stacked = self.offset.offset(intermediate);
+
+        let mut ret: Vec<Option<StackSeries>> = vec![None; row_len];
+        for (index, (j, points)) in order.into_iter().zip(stacked).enumerate() {
+            ret[j] = Some(StackSeries {
+                key: self.keys.as_ref().and_then(|keys| keys.get(j).cloned()),
+                index,
+                points,
+            });
+        }
 
-        ret
+        ret.into_iter()
+            .map(|series| series.expect("Every series is stacked exactly once"))
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{assert_delta, test::DELTA};
+
     use super::*;
     #[test]
     fn example_from_readme() {
@@ -121,13 +179,16 @@ mod tests {
 
         let s = Stack::new(data.iter()).stack();
 
-        assert_eq!(s[0], vec![0.0..3840.0, 0.0..1600.0, 0.0..640.0, 0.0..320.0]);
         assert_eq!(
-            s[1],
+            s[0].points,
+            vec![0.0..3840.0, 0.0..1600.0, 0.0..640.0, 0.0..320.0]
+        );
+        assert_eq!(
+            s[1].points,
             vec![3840.0..5760.0, 1600.0..3040.0, 640.0..1600.0, 320.0..800.0]
         );
         assert_eq!(
-            s[2],
+            s[2].points,
             vec![
                 5760.0..6720.0,
                 3040.0..4000.0,
@@ -136,7 +197,7 @@ mod tests {
             ]
         );
         assert_eq!(
-            s[3],
+            s[3].points,
             vec![
                 6720.0..7120.0,
                 4000.0..4400.0,
@@ -145,4 +206,121 @@ mod tests {
             ]
         );
     }
+
+    #[derive(Debug)]
+    struct Fruit(f64, f64, f64);
+
+    impl Stackable for &Fruit {
+        fn values(&self) -> Vec<f64> {
+            vec![self.0, self.1, self.2]
+        }
+    }
+
+    #[test]
+    fn keys_and_index() {
+        let data = vec![Fruit(1.0, 2.0, 3.0), Fruit(4.0, 5.0, 6.0)];
+
+        let s = Stack::new(data.iter())
+            .keys(&["apples", "bananas", "cherries"])
+            .stack();
+
+        let keys: Vec<_> = s.iter().map(|series| series.key.clone().unwrap()).collect();
+        assert_eq!(vec!["apples", "bananas", "cherries"], keys);
+        assert_eq!(
+            vec![0, 1, 2],
+            s.iter().map(|series| series.index).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn order_determines_stacking_but_not_output() {
+        use crate::shape::order::OrderDescending;
+
+        let data = vec![Fruit(1.0, 3.0, 2.0), Fruit(1.0, 3.0, 2.0)];
+
+        let s = Stack::new(data.iter())
+            .order(Box::new(OrderDescending {}))
+            .stack();
+
+        assert_eq!(
+            vec![2, 0, 1],
+            s.iter().map(|series| series.index).collect::<Vec<_>>()
+        );
+        assert_eq!(vec![5.0..6.0, 5.0..6.0], s[0].points);
+        assert_eq!(vec![0.0..3.0, 0.0..3.0], s[1].points);
+        assert_eq!(vec![3.0..5.0, 3.0..5.0], s[2].points);
+        assert_eq!(None, s[0].key);
+    }
+
+    #[test]
+    fn nan_values_do_not_move_the_series_above() {
+        let data = vec![Fruit(1.0, std::f64::NAN, 3.0), Fruit(1.0, 2.0, 3.0)];
+
+        let s = Stack::new(data.iter()).stack();
+
+        assert_eq!(vec![0.0..1.0, 0.0..1.0], s[0].points);
+        assert_eq!(1.0, s[1].points[0].start);
+        assert!(s[1].points[0].end.is_nan());
+        assert_eq!(1.0..3.0, s[1].points[1]);
+        assert_eq!(vec![1.0..4.0, 3.0..6.0], s[2].points);
+    }
+
+    #[test]
+    fn silhouette_offset_with_nan_values() {
+        use crate::shape::offset::OffsetSilhouette;
+
+        let data = vec![Fruit(1.0, std::f64::NAN, 3.0), Fruit(1.0, 2.0, 3.0)];
+
+        let s = Stack::new(data.iter())
+            .offset(Box::new(OffsetSilhouette {}))
+            .stack();
+
+        assert_eq!(vec![-2.0..-1.0, -3.0..-2.0], s[0].points);
+        assert_eq!(-1.0, s[1].points[0].start);
+        assert!(s[1].points[0].end.is_nan());
+        assert_eq!(-2.0..0.0, s[1].points[1]);
+        assert_eq!(vec![-1.0..2.0, 0.0..3.0], s[2].points);
+    }
+
+    #[test]
+    fn wiggle_offset_with_nan_values() {
+        use crate::shape::offset::OffsetWiggle;
+
+        let data = vec![Fruit(1.0, std::f64::NAN, 3.0), Fruit(1.0, 2.0, 3.0)];
+
+        let s = Stack::new(data.iter())
+            .offset(Box::new(OffsetWiggle {}))
+            .stack();
+
+        assert_eq!(0.0..1.0, s[0].points[0]);
+        assert_eq!(1.0, s[1].points[0].start);
+        assert!(s[1].points[0].end.is_nan());
+        assert_eq!(1.0..4.0, s[2].points[0]);
+
+        let bounds: Vec<_> = s
+            .iter()
+            .flat_map(|series| vec![series.points[1].start, series.points[1].end])
+            .collect();
+        let expected = [-4.0, -1.0, -1.0, 5.0, 5.0, 14.0];
+        for (expected, bound) in expected.iter().zip(bounds) {
+            assert_delta!(expected / 3.0, bound, DELTA);
+        }
+    }
+
+    #[test]
+    fn diverging_offset_with_nan_values() {
+        use crate::shape::offset::OffsetDiverging;
+
+        let data = vec![Fruit(1.0, std::f64::NAN, -3.0), Fruit(1.0, 2.0, -3.0)];
+
+        let s = Stack::new(data.iter())
+            .offset(Box::new(OffsetDiverging {}))
+            .stack();
+
+        assert_eq!(vec![0.0..1.0, 0.0..1.0], s[0].points);
+        assert_eq!(0.0, s[1].points[0].start);
+        assert!(s[1].points[0].end.is_nan());
+        assert_eq!(1.0..3.0, s[1].points[1]);
+        assert_eq!(vec![-3.0..0.0, -3.0..0.0], s[2].points);
+    }
 }